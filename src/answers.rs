@@ -0,0 +1,94 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{runner, solutions::Run};
+
+pub fn path() -> PathBuf {
+    runner::root().join("inputs/answers.txt")
+}
+
+/// Parses recorded answers written as `day part answer`, one per line.
+/// Empty lines and lines starting with `#` are ignored.
+pub fn parse(input: &str) -> Result<HashMap<(u8, u8), String>, String> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let parts = line.splitn(3, ' ').collect::<Vec<_>>();
+            match parts[..] {
+                [day, part, answer] => match (day.parse(), part.parse()) {
+                    (Ok(day), Ok(part)) => Ok(((day, part), answer.replace("\\n", "\n"))),
+                    _ => Err(format!("line {}: invalid day or part: {}", i + 1, line)),
+                },
+                _ => Err(format!(
+                    "line {}: expected `day part answer`: {}",
+                    i + 1,
+                    line
+                )),
+            }
+        })
+        .collect()
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: String, actual: String },
+    Failed(String),
+    Unknown,
+}
+
+/// Compares every part result with its recorded answer.
+pub fn verify(
+    results: &[(u8, Run)],
+    answers: &HashMap<(u8, u8), String>,
+) -> Vec<(u8, u8, Verdict)> {
+    results
+        .iter()
+        .flat_map(|(day, run)| {
+            run.parts.iter().map(move |result| {
+                let verdict = match (&result.answer, answers.get(&(*day, result.part))) {
+                    (Err(e), _) => Verdict::Failed(e.clone()),
+                    (Ok(_), None) => Verdict::Unknown,
+                    (Ok(answer), Some(expected)) => match answer.to_string() == *expected {
+                        true => Verdict::Correct,
+                        false => Verdict::Incorrect {
+                            expected: expected.clone(),
+                            actual: answer.to_string(),
+                        },
+                    },
+                };
+                (*day, result.part, verdict)
+            })
+        })
+        .collect()
+}
+
+pub fn print_verdicts(verdicts: &[(u8, u8, Verdict)]) {
+    println!("{:>3}  {:>4}  Result", "Day", "Part");
+    for (day, part, verdict) in verdicts {
+        let result = match verdict {
+            Verdict::Correct => "ok".to_owned(),
+            Verdict::Incorrect { expected, actual } => {
+                format!("MISMATCH: expected {:?}, got {:?}", expected, actual)
+            }
+            Verdict::Failed(e) => format!("FAILED: {}", e),
+            Verdict::Unknown => "no recorded answer".to_owned(),
+        };
+        println!("{:>3}  {:>4}  {}", day, part, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse("# comment\n1 1 24000\n\n10 2 #.\\n.#\n").unwrap();
+        assert_eq!(answers[&(1, 1)], "24000");
+        assert_eq!(answers[&(10, 2)], "#.\n.#");
+        assert!(parse("1 24000").is_err());
+        assert!(parse("x 1 24000").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::{error, fmt};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    /// A line of input that could not be parsed. `line` starts at 1.
+    Parse {
+        day: Option<u8>,
+        line: usize,
+        text: String,
+        reason: String,
+    },
+    /// Input that is malformed as a whole rather than on a single line.
+    Input { day: Option<u8>, reason: String },
+}
+
+impl Error {
+    pub fn parse(line: usize, text: &str, reason: impl Into<String>) -> Error {
+        Error::Parse {
+            day: None,
+            line,
+            text: text.to_owned(),
+            reason: reason.into(),
+        }
+    }
+    pub fn input(reason: impl Into<String>) -> Error {
+        Error::Input {
+            day: None,
+            reason: reason.into(),
+        }
+    }
+    /// Shifts the line number by `lines`, for errors from parsing a section
+    /// that starts part way into the input.
+    pub fn offset(self, lines: usize) -> Error {
+        match self {
+            Error::Parse {
+                day,
+                line,
+                text,
+                reason,
+            } => Error::Parse {
+                day,
+                line: line + lines,
+                text,
+                reason,
+            },
+            e => e,
+        }
+    }
+    /// Attributes the error to `day`, unless it already is.
+    pub fn with_day(self, day: u8) -> Error {
+        match self {
+            Error::Parse {
+                day: None,
+                line,
+                text,
+                reason,
+            } => Error::Parse {
+                day: Some(day),
+                line,
+                text,
+                reason,
+            },
+            Error::Input { day: None, reason } => Error::Input {
+                day: Some(day),
+                reason,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let day = match self {
+            Error::Parse { day, .. } | Error::Input { day, .. } => day,
+        };
+        if let Some(day) = day {
+            write!(f, "day {}, ", day)?;
+        }
+        match self {
+            Error::Parse {
+                line, text, reason, ..
+            } => write!(f, "line {}: {}: `{}`", line, reason, text),
+            Error::Input { reason, .. } => write!(f, "input: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Parses every line of `input` with `f`, attaching the line number and text
+/// to the first line that fails.
+pub fn parse_lines<T>(
+    input: &str,
+    f: impl Fn(&str) -> std::result::Result<T, String>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|reason| Error::parse(i + 1, line, reason)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines("1\n2", |l| l.parse::<u8>().map_err(|_| "nope".to_owned())),
+            Ok(vec![1, 2])
+        );
+        let e = parse_lines("1\nx", |l| {
+            l.parse::<u8>().map_err(|_| "invalid number".to_owned())
+        })
+        .unwrap_err()
+        .with_day(1);
+        assert_eq!(e.to_string(), "day 1, line 2: invalid number: `x`");
+    }
+}
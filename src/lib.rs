@@ -0,0 +1,10 @@
+//! Solutions to Advent of Code 2022, along with the grid and coordinate
+//! utilities they are built on.
+
+pub mod answers;
+pub mod error;
+pub mod params;
+pub mod runner;
+pub mod scaffold;
+pub mod solutions;
+pub mod utils;
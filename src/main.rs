@@ -1,70 +1,255 @@
-use std::{
-    env,
-    io::{self, Read},
-};
+use std::{env, fs, path::PathBuf, process};
 
-use solutions::{
-    day1, day10, day11, day12, day13, day14, day15, day16, day2, day3, day4, day5, day6, day7,
-    day8, day9,
+use aoc_2022::{
+    answers::{self, Verdict},
+    params::Params,
+    runner, scaffold,
+    solutions::{self, day13, Config, Run, Solver},
 };
 
-mod solutions;
-mod utils;
+const USAGE: &str = "Usage:
+    aoc-2022 <day> [--input <path>] [--format text|json] [--part 1|2] [--param <name>=<value>]...
+    aoc-2022 13 --trace [--input <path>]
+    aoc-2022 all [--format text|json] [--part 1|2]
+    aoc-2022 bench <day> [runs] [--input <path>] [--part 1|2] [--param <name>=<value>]...
+    aoc-2022 verify
+    aoc-2022 new <day>
+
+Input for a single day is read from --input if given, otherwise from stdin
+when something is piped in, otherwise from inputs/day<day>.in.
+
+Some days take params, e.g. the row to check in day 15, so the example input
+can be solved with `aoc-2022 15 --input example.in --param row=10`. An
+unknown param name lists the ones the day accepts.
+
+--trace prints what decided the order of each pair of day 13 packets.";
+
+struct Options {
+    command: Vec<String>,
+    input: Option<PathBuf>,
+    format: Format,
+    config: Config,
+    trace: bool,
+}
+
+#[derive(PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let day = &args[1];
-    let mut input = String::new();
-    io::stdin().lock().read_to_string(&mut input).unwrap();
-    match day.as_str() {
-        "1" => {
-            day1::run(&input);
-        }
-        "2" => {
-            day2::run(&input);
-        }
-        "3" => {
-            day3::run(&input);
-        }
-        "4" => {
-            day4::run(&input);
-        }
-        "5" => {
-            day5::run(&input);
-        }
-        "6" => {
-            day6::run(&input);
-        }
-        "7" => {
-            day7::run(&input);
-        }
-        "8" => {
-            day8::run(&input);
-        }
-        "9" => {
-            day9::run(&input);
-        }
-        "10" => {
-            day10::run(&input);
-        }
-        "11" => {
-            day11::run(&input);
-        }
-        "12" => {
-            day12::run(&input);
-        }
-        "13" => {
-            day13::run(&input);
-        }
-        "14" => {
-            day14::run(&input);
-        }
-        "15" => {
-            day15::run(&input);
-        }
-        "16" => {
-            day16::run(&input);
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
         }
-        _ => panic!("Solution not found"),
     };
+    let command = options
+        .command
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>();
+    if let Err(e) = check_params(&command, &options.config) {
+        eprintln!("{}\n\n{}", e, USAGE);
+        process::exit(2);
+    }
+    let result = match command[..] {
+        [day] if options.trace => trace(day, &options),
+        _ if options.trace => Err("--trace only applies to day 13".to_owned()),
+        ["all"] => all(&options),
+        ["bench", day] => bench(day, "10", &options),
+        ["bench", day, runs] => bench(day, runs, &options),
+        ["verify"] => verify(),
+        ["new", day] => new(day),
+        [day] => run(day, &options),
+        _ => Err("Unexpected arguments".to_owned()),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        command: Vec::new(),
+        input: None,
+        format: Format::Text,
+        config: Config::default(),
+        trace: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "--input" => match args.next() {
+                Some(path) => options.input = Some(PathBuf::from(path)),
+                None => return Err("--input requires a path".to_owned()),
+            },
+            "--format" => {
+                options.format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err("--format must be text or json".to_owned()),
+                }
+            }
+            "--part" => {
+                options.config.part = match args.next().as_deref() {
+                    Some("1") => Some(1),
+                    Some("2") => Some(2),
+                    _ => return Err("--part must be 1 or 2".to_owned()),
+                }
+            }
+            "--trace" => options.trace = true,
+            "--param" => match args.next().as_deref().and_then(|p| p.split_once('=')) {
+                Some((name, value)) => options
+                    .config
+                    .params
+                    .push((name.to_owned(), value.to_owned())),
+                None => return Err("--param requires <name>=<value>".to_owned()),
+            },
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ => options.command.push(arg),
+        }
+    }
+    match options.command.is_empty() {
+        true => Err("Missing command".to_owned()),
+        false => Ok(options),
+    }
+}
+
+/// Rejects `--param` values the day doesn't accept before any input is
+/// read or solved.
+fn check_params(command: &[&str], config: &Config) -> Result<(), String> {
+    let day = match command {
+        ["bench", day, ..] | [day] => day,
+        _ => return Ok(()),
+    };
+    match day.parse().ok().and_then(solutions::get) {
+        Some(solver) => Params::new(solver.params(), &config.params).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+fn all(options: &Options) -> Result<(), String> {
+    if !options.config.params.is_empty() {
+        return Err("--param applies to a single day".to_owned());
+    }
+    let results = runner::run_all(&options.config);
+    match options.format {
+        Format::Text => runner::print_table(&results),
+        Format::Json => println!("{}", runner::to_json(&results)),
+    }
+    let failed = results.iter().filter(|(_, run)| has_failed(run)).count();
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} of {} days failed", failed, results.len())),
+    }
+}
+
+/// Whether the day could not be solved, or any of its parts failed.
+fn has_failed(run: &Run) -> bool {
+    run.error.is_some() || run.parts.iter().any(|part| part.answer.is_err())
+}
+
+fn solver(day: &str) -> Result<(u8, &'static dyn Solver), String> {
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid day: {}", day))?;
+    match solutions::get(day) {
+        Some(solver) => Ok((day, solver)),
+        None => Err(format!("Solution not found for day {}", day)),
+    }
+}
+
+fn run(day: &str, options: &Options) -> Result<(), String> {
+    let (day, solver) = solver(day)?;
+    let input = runner::read_input(day, options.input.as_deref())?;
+    let run = solver.solve(&input, &options.config);
+    let failed = has_failed(&run);
+    if options.format == Format::Json {
+        println!("{}", runner::to_json(&[(day, run)]));
+        return match failed {
+            true => Err(format!("Day {} failed", day)),
+            false => Ok(()),
+        };
+    }
+    if let Some(e) = run.error {
+        return Err(e);
+    }
+    println!("Parse: {:.2?}", run.parse);
+    for result in run.parts {
+        match result.answer {
+            Ok(answer) => {
+                let answer = answer.to_string();
+                match answer.contains('\n') {
+                    true => println!("Part {}: - {:.2?}\n{}", result.part, result.elapsed, answer),
+                    false => println!("Part {}: {} - {:.2?}", result.part, answer, result.elapsed),
+                }
+            }
+            Err(e) => println!("Part {} failed: {}", result.part, e),
+        }
+    }
+    match failed {
+        true => Err(format!("Day {} failed", day)),
+        false => Ok(()),
+    }
+}
+
+fn trace(day: &str, options: &Options) -> Result<(), String> {
+    if day != "13" {
+        return Err("--trace only applies to day 13".to_owned());
+    }
+    let input = runner::read_input(13, options.input.as_deref())?;
+    let pairs = day13::parse(&input).map_err(|e| e.with_day(13).to_string())?;
+    for (i, (left, right)) in pairs.iter().enumerate() {
+        println!("Pair {}: {}", i + 1, day13::explain(left, right));
+    }
+    Ok(())
+}
+
+fn bench(day: &str, runs: &str, options: &Options) -> Result<(), String> {
+    let (day, solver) = solver(day)?;
+    let runs = runs
+        .parse::<usize>()
+        .ok()
+        .filter(|runs| *runs > 0)
+        .ok_or_else(|| format!("Invalid number of runs: {}", runs))?;
+    let input = runner::read_input(day, options.input.as_deref())?;
+    let stats = runner::bench(solver, &input, &options.config, runs)
+        .map_err(|e| format!("Day {} failed: {}", day, e))?;
+    runner::print_bench(day, runs, &stats);
+    Ok(())
+}
+
+fn verify() -> Result<(), String> {
+    let path = answers::path();
+    let answers =
+        fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let answers = answers::parse(&answers).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let verdicts = answers::verify(&runner::run_all(&Config::default()), &answers);
+    answers::print_verdicts(&verdicts);
+    match verdicts
+        .iter()
+        .any(|(_, _, v)| matches!(v, Verdict::Incorrect { .. } | Verdict::Failed(_)))
+    {
+        true => Err("Verification failed".to_owned()),
+        false => Ok(()),
+    }
+}
+
+fn new(day: &str) -> Result<(), String> {
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid day: {}", day))?;
+    scaffold::create(&runner::root(), day)?;
+    println!(
+        "Created src/solutions/day{}.rs and inputs/day{}.in",
+        day, day
+    );
+    Ok(())
 }
@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    str::FromStr,
+};
+
+/// A parameter a day accepts through `--param name=value`.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+    pub kind: Kind,
+}
+
+/// The values a parameter accepts, checked before any part runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A count or size, read as `usize`.
+    Unsigned,
+    /// A position that may be negative, read as `i64`.
+    Integer,
+}
+
+impl Kind {
+    fn check(&self, value: &str) -> Result<(), String> {
+        let valid = match self {
+            Kind::Unsigned => value.parse::<usize>().is_ok(),
+            Kind::Integer => value.parse::<i64>().is_ok(),
+        };
+        match valid {
+            true => Ok(()),
+            false => Err(format!("expected {}", self)),
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Unsigned => write!(f, "a non-negative integer"),
+            Kind::Integer => write!(f, "an integer"),
+        }
+    }
+}
+
+/// Values for a day's declared parameters, defaults filled in.
+#[derive(Debug)]
+pub struct Params {
+    values: HashMap<&'static str, String>,
+}
+
+impl Params {
+    pub fn new(
+        declared: &'static [Param],
+        overrides: &[(String, String)],
+    ) -> Result<Params, String> {
+        let mut values = declared
+            .iter()
+            .map(|p| (p.name, p.default.to_owned()))
+            .collect::<HashMap<_, _>>();
+        for (name, value) in overrides {
+            match declared.iter().find(|p| p.name == name) {
+                Some(p) => {
+                    p.kind.check(value).map_err(|e| {
+                        format!("Invalid value {:?} for param {}, {}", value, name, e)
+                    })?;
+                    values.insert(p.name, value.to_owned())
+                }
+                None => return Err(unknown(name, declared)),
+            };
+        }
+        Ok(Params { values })
+    }
+    /// Value of a declared parameter. Values are checked against the
+    /// parameter's kind up front, so this only panics if `T` can't hold it.
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        let value = self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("Undeclared param: {}", name));
+        match value.parse() {
+            Ok(v) => v,
+            Err(e) => panic!("Invalid value {:?} for param {}: {:?}", value, name, e),
+        }
+    }
+}
+
+fn unknown(name: &str, declared: &[Param]) -> String {
+    match declared.is_empty() {
+        true => format!("Unknown param {}, this day takes no params", name),
+        false => format!(
+            "Unknown param {}, expected one of:\n{}",
+            name,
+            declared
+                .iter()
+                .map(|p| format!("    {}={}  {}", p.name, p.default, p.help))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[Param {
+        name: "rounds",
+        default: "20",
+        help: "number of rounds",
+        kind: Kind::Unsigned,
+    }];
+
+    #[test]
+    fn test_params() {
+        assert_eq!(Params::new(PARAMS, &[]).unwrap().get::<usize>("rounds"), 20);
+        let overrides = vec![("rounds".to_owned(), "5".to_owned())];
+        assert_eq!(
+            Params::new(PARAMS, &overrides)
+                .unwrap()
+                .get::<usize>("rounds"),
+            5
+        );
+        let overrides = vec![("turns".to_owned(), "5".to_owned())];
+        assert!(Params::new(PARAMS, &overrides).is_err());
+        let overrides = vec![("rounds".to_owned(), "-1".to_owned())];
+        assert_eq!(
+            Params::new(PARAMS, &overrides).unwrap_err(),
+            "Invalid value \"-1\" for param rounds, expected a non-negative integer"
+        );
+    }
+}
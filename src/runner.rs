@@ -0,0 +1,331 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    env, fs,
+    io::{self, IsTerminal, Read},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Once,
+    time::{Duration, Instant},
+};
+
+use crate::solutions::{self, Answer, Config, Run, Solver};
+
+thread_local! {
+    /// Whether this thread is inside `timed`, whose panics are reported as
+    /// errors rather than by the panic hook.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, measuring how long it takes and turning a panic into an error.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (Result<T, String>, Duration) {
+    static SILENCE: Once = Once::new();
+    SILENCE.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                hook(info)
+            }
+        }));
+    });
+    QUIET.set(true);
+    let now = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+    let elapsed = now.elapsed();
+    QUIET.set(false);
+    (result, elapsed)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_owned(),
+        },
+    }
+}
+
+/// The directory `inputs/` and `src/` are resolved against: the package
+/// that `cargo run` was started for, or the current directory when the
+/// binary is run directly. An empty path joins as a relative one.
+pub fn root() -> PathBuf {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    root().join(format!("inputs/day{}.in", day))
+}
+
+/// Reads the input for `day` from `path` if given, otherwise from stdin when
+/// it is piped, falling back to the default location in `inputs/` when
+/// nothing was piped, e.g. stdin is `/dev/null` or a terminal.
+pub fn read_input(day: u8, path: Option<&Path>) -> Result<String, String> {
+    let path = match path {
+        Some(path) => path.to_owned(),
+        None if !io::stdin().is_terminal() => {
+            let mut input = String::new();
+            io::stdin()
+                .lock()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            if !input.is_empty() {
+                return Ok(input);
+            }
+            input_path(day)
+        }
+        None => input_path(day),
+    };
+    fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))
+}
+
+/// Runs every registered day against its input in `inputs/`.
+pub fn run_all(config: &Config) -> Vec<(u8, Run)> {
+    solutions::SOLUTIONS
+        .iter()
+        .map(|solver| {
+            let day = solver.day();
+            let path = input_path(day);
+            let run = match fs::read_to_string(&path) {
+                Ok(input) => solver.solve(&input, config),
+                Err(e) => Run::failed(
+                    config,
+                    Duration::ZERO,
+                    format!("cannot read {}: {}", path.display(), e),
+                ),
+            };
+            (day, run)
+        })
+        .collect()
+}
+
+pub fn print_table(results: &[(u8, Run)]) {
+    let rows = results
+        .iter()
+        .flat_map(|(day, run)| {
+            run.parts.iter().map(move |result| {
+                let answer = match &result.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => format!("FAILED: {}", e),
+                };
+                let parse = match result.part {
+                    1 => format!("{:.2?}", run.parse),
+                    _ => String::new(),
+                };
+                (
+                    day.to_string(),
+                    result.part.to_string(),
+                    answer,
+                    parse,
+                    format!("{:.2?}", result.elapsed),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let answer_width = rows
+        .iter()
+        .flat_map(|(_, _, answer, _, _)| answer.lines())
+        .map(|line| line.chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap();
+    println!(
+        "{:>3}  {:>4}  {:<answer_width$}  {:>10}  {:>10}",
+        "Day", "Part", "Answer", "Parse", "Time"
+    );
+    for (day, part, answer, parse, elapsed) in rows {
+        let mut lines = answer.lines();
+        let first = lines.next().unwrap_or_default();
+        println!(
+            "{:>3}  {:>4}  {:<answer_width$}  {:>10}  {:>10}",
+            day, part, first, parse, elapsed
+        );
+        for line in lines {
+            println!("{:>3}  {:>4}  {}", "", "", line);
+        }
+    }
+}
+
+/// Renders results as a JSON array with one object per part.
+pub fn to_json(results: &[(u8, Run)]) -> String {
+    let objects = results
+        .iter()
+        .flat_map(|(day, run)| {
+            run.parts.iter().map(move |result| {
+                let (answer, error) = match &result.answer {
+                    Ok(Answer::Number(n)) => (n.to_string(), "null".to_owned()),
+                    Ok(Answer::Text(s)) => (json_string(s), "null".to_owned()),
+                    Err(e) => ("null".to_owned(), json_string(e)),
+                };
+                format!(
+                    "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"duration_ns\": {}, \"error\": {}}}",
+                    day,
+                    result.part,
+                    answer,
+                    result.elapsed.as_nanos(),
+                    error
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Minimum, median and maximum of repeated timings.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Solves a day `runs` times, returning timing statistics for parsing and
+/// each part, or the first error encountered.
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    config: &Config,
+    runs: usize,
+) -> Result<Vec<(String, Stats)>, String> {
+    let mut parse = Vec::new();
+    let mut parts: Vec<(u8, Vec<Duration>)> = Vec::new();
+    for _ in 0..runs {
+        let run = solver.solve(input, config);
+        if let Some(e) = run.error {
+            return Err(e);
+        }
+        parse.push(run.parse);
+        for (i, result) in run.parts.into_iter().enumerate() {
+            if let Err(e) = result.answer {
+                return Err(format!("part {} failed: {}", result.part, e));
+            }
+            match parts.get_mut(i) {
+                Some((_, samples)) => samples.push(result.elapsed),
+                None => parts.push((result.part, vec![result.elapsed])),
+            }
+        }
+    }
+    let mut stats = vec![("Parse".to_owned(), Stats::new(parse))];
+    stats.extend(
+        parts
+            .into_iter()
+            .map(|(part, samples)| (format!("Part {}", part), Stats::new(samples))),
+    );
+    Ok(stats)
+}
+
+pub fn print_bench(day: u8, runs: usize, stats: &[(String, Stats)]) {
+    println!(
+        "{:<10}  {:>10}  {:>10}  {:>10}",
+        format!("Day {}", day),
+        "Min",
+        "Median",
+        "Max"
+    );
+    for (name, s) in stats {
+        println!(
+            "{:<10}  {:>10}  {:>10}  {:>10}",
+            name,
+            format!("{:.2?}", s.min),
+            format!("{:.2?}", s.median),
+            format!("{:.2?}", s.max)
+        );
+    }
+    println!("({} runs)", runs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::PartResult;
+
+    #[test]
+    fn test_timed() {
+        assert_eq!(timed(|| 42).0, Ok(42));
+        assert_eq!(
+            timed(|| -> u32 { panic!("Invalid state") }).0,
+            Err("Invalid state".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let results = vec![(
+            10,
+            Run {
+                parse: Duration::ZERO,
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Ok(Answer::Number(13140)),
+                        elapsed: Duration::from_nanos(1500),
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: Ok(Answer::Text("#.\n\"a\"".to_owned())),
+                        elapsed: Duration::from_nanos(20),
+                    },
+                ],
+                error: None,
+            },
+        )];
+        assert_eq!(
+            to_json(&results),
+            r##"[
+  {"day": 10, "part": 1, "answer": 13140, "duration_ns": 1500, "error": null},
+  {"day": 10, "part": 2, "answer": "#.\n\"a\"", "duration_ns": 20, "error": null}
+]"##
+        );
+        let failed = vec![(
+            1,
+            Run::failed(&Config::default(), Duration::ZERO, "bad\tinput".to_owned()),
+        )];
+        assert!(
+            to_json(&failed).contains(r#""answer": null, "duration_ns": 0, "error": "bad\tinput""#)
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 2, 4].map(Duration::from_millis).to_vec();
+        assert_eq!(
+            Stats::new(samples),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+}
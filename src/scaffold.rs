@@ -0,0 +1,177 @@
+use std::{fs, path::Path};
+
+/// Creates `src/solutions/day{day}.rs` from a template, registers it in
+/// `src/solutions/mod.rs` and adds an empty `inputs/day{day}.in`.
+pub fn create(root: &Path, day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day: {}", day));
+    }
+    let module = root.join(format!("src/solutions/day{}.rs", day));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    let mod_rs = root.join("src/solutions/mod.rs");
+    let registered = register(&read(&mod_rs)?, day)?;
+    write(&module, &template(day))?;
+    write(&mod_rs, &registered)?;
+    let input = root.join(format!("inputs/day{}.in", day));
+    if !input.exists() {
+        write(&input, "")?;
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+pub fn template(day: u8) -> String {
+    format!(
+        r#"use super::{{Answer, Solution}};
+use crate::{{
+    error::{{self, Result}},
+    params::Params,
+}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    fn parse(input: &str) -> Result<Self::Input> {{
+        parse(input)
+    }}
+    fn part1(input: &Self::Input, _: &Params) -> Answer {{
+        part1(input).into()
+    }}
+    fn part2(input: &Self::Input, _: &Params) -> Answer {{
+        part2(input).into()
+    }}
+}}
+
+pub fn part1(lines: &[String]) -> usize {{
+    lines.len()
+}}
+
+pub fn part2(lines: &[String]) -> usize {{
+    lines.len()
+}}
+
+pub fn parse(input: &str) -> Result<Vec<String>> {{
+    error::parse_lines(input, |line| Ok(line.to_owned()))
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    fn input() -> &'static str {{
+        ""
+    }}
+
+    #[test]
+    fn test_part1() {{
+        assert_eq!(part1(&parse(input()).unwrap()), 0);
+    }}
+
+    #[test]
+    fn test_part2() {{
+        assert_eq!(part2(&parse(input()).unwrap()), 0);
+    }}
+}}
+"#
+    )
+}
+
+/// Adds `pub mod day{day};` and the solution to the registry in the given
+/// `solutions/mod.rs` source, keeping both lists in their existing order.
+pub fn register(mod_rs: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let mod_line = format!("pub mod {};", module);
+    let entry = format!("    &{}::Day{},", module, day);
+    if mod_rs.lines().any(|line| line == mod_line) {
+        return Err(format!("{} is already registered", module));
+    }
+    let mut lines = mod_rs.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let last_mod = *mods.last().ok_or("no `pub mod dayN;` declarations found")?;
+    let mod_at = mods
+        .iter()
+        .find(|i| lines[**i].trim_end_matches(';') > mod_line.trim_end_matches(';'))
+        .copied()
+        .unwrap_or(last_mod + 1);
+    lines.insert(mod_at, mod_line);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLUTIONS"))
+        .ok_or("no SOLUTIONS registry found")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or("unterminated SOLUTIONS registry")?;
+    let entry_at = (start + 1..end)
+        .find(|i| registered_day(&lines[*i]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(entry_at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn registered_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix("&day")?
+        .split("::")
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let mod_rs = "pub mod day1;
+pub mod day10;
+pub mod day2;
+
+pub const SOLUTIONS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day10::Day10,
+];
+";
+        assert_eq!(
+            register(mod_rs, 3).unwrap(),
+            "pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+
+pub const SOLUTIONS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day10::Day10,
+];
+"
+        );
+        assert!(register(mod_rs, 17)
+            .unwrap()
+            .contains("pub mod day10;\npub mod day17;\npub mod day2;"));
+        assert!(register(mod_rs, 2).is_err());
+    }
+}
@@ -1,25 +1,50 @@
-pub fn run(input: &str) {
-    println!("part 1: {}", part1(input));
-    println!("part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{Error, Result},
+    params::Params,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<usize>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(|group| group.split('\n').map(|x| x.parse::<usize>().unwrap()).sum())
-        .max()
-        .unwrap()
+pub fn part1(elfs: &[usize]) -> usize {
+    *elfs.iter().max().unwrap()
 }
 
-fn part2(input: &str) -> usize {
-    let mut elfs: Vec<usize> = input
-        .split("\n\n")
-        .map(|group| group.split('\n').map(|x| x.parse::<usize>().unwrap()).sum())
-        .collect();
+pub fn part2(elfs: &[usize]) -> usize {
+    let mut elfs = elfs.to_vec();
     elfs.sort();
     elfs.into_iter().rev().take(3).sum()
 }
 
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let mut elfs = vec![0];
+    for (i, line) in input.lines().enumerate() {
+        match line {
+            "" => elfs.push(0),
+            _ => {
+                *elfs.last_mut().unwrap() += line
+                    .parse::<usize>()
+                    .map_err(|_| Error::parse(i + 1, line, "invalid calories"))?
+            }
+        }
+    }
+    Ok(elfs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,7 +65,7 @@ mod tests {
 9000
 
 10000";
-        assert_eq!(part1(input), 24000);
+        assert_eq!(part1(&parse(input).unwrap()), 24000);
     }
 
     #[test]
@@ -59,6 +84,6 @@ mod tests {
 9000
 
 10000";
-        assert_eq!(part2(input), 45000);
+        assert_eq!(part2(&parse(input).unwrap()), 45000);
     }
 }
@@ -1,67 +1,192 @@
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: \n{}", part2(input));
-}
-
-fn part1(input: &str) -> i64 {
-    let mut signal_strengths: Vec<i64> = vec![];
-    let mut x = 1;
-    let mut cycle_count = 0;
-    for (cycle_time, value) in parse(input) {
-        for _ in 0..cycle_time {
-            cycle_count += 1;
-            if (cycle_count - 20) % 40 == 0 {
-                signal_strengths.push(cycle_count * x);
-            }
+use std::str::FromStr;
+
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+    utils::{coordinate::Coordinate, grid::Grid, ocr, render},
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Instruction>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
+}
+
+pub fn part1(program: &[Instruction]) -> i64 {
+    let mut signal = SignalStrength { sum: 0 };
+    Cpu::new(program).run(&mut signal);
+    signal.sum
+}
+
+pub fn part2(program: &[Instruction]) -> String {
+    let mut crt = Crt::new();
+    Cpu::new(program).run(&mut crt);
+    // Screens that don't spell anything, like the example's, are shown as
+    // they were drawn, followed by why they couldn't be read.
+    ocr::read(&crt.screen).unwrap_or_else(|e| format!("{}\n({})", crt.render(), e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl Instruction {
+    /// Number of cycles the instruction takes to complete.
+    pub fn cycles(&self) -> u32 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
         }
-        x += value;
-    }
-    signal_strengths.iter().sum()
-}
-
-fn part2(input: &str) -> String {
-    let mut x = 1;
-    let mut cycle_count = 0i64;
-    let mut screen = [[' '; 40]; 6];
-    for (cycle_time, value) in parse(input) {
-        for _ in 0..cycle_time {
-            cycle_count += 1;
-            let col = (cycle_count - 1) % 40;
-            let c = match col.abs_diff(x) <= 1 {
-                true => '#',
-                false => '.',
-            };
-            screen[((cycle_count - 1) / 40) as usize][col as usize] = c;
+    }
+    fn execute(&self, registers: &mut Registers) {
+        match self {
+            Instruction::Noop => {}
+            Instruction::Addx(value) => registers.x += value,
         }
-        x += value;
     }
-    render(screen)
 }
 
-fn render(screen: [[char; 40]; 6]) -> String {
-    let mut result = String::new();
-    for i in 0..screen.len() {
-        for j in 0..screen[0].len() {
-            result.push(screen[i][j]);
+impl FromStr for Instruction {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Instruction, String> {
+        match s.split(' ').collect::<Vec<&str>>()[..] {
+            ["noop"] => Ok(Instruction::Noop),
+            ["addx", value] => match value.parse::<i64>() {
+                Ok(value) => Ok(Instruction::Addx(value)),
+                Err(_) => Err(format!("invalid value {:?}", value)),
+            },
+            _ => Err("unknown instruction".to_owned()),
         }
-        result.push('\n');
-    }
-    result
-}
-
-fn parse(input: &str) -> Vec<(u8, i64)> {
-    input
-        .lines()
-        .map(|line| match line {
-            "noop" => (1, 0),
-            _ => (
-                2,
-                line.split(' ').collect::<Vec<&str>>()[1]
-                    .parse::<i64>()
-                    .unwrap(),
-            ),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i64,
+}
+
+/// Hook called by the CPU during every cycle, before the instruction being
+/// executed has taken effect.
+pub trait Observer {
+    fn during_cycle(&mut self, cycle: u64, registers: &Registers);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(u64, &Registers),
+{
+    fn during_cycle(&mut self, cycle: u64, registers: &Registers) {
+        self(cycle, registers)
+    }
+}
+
+pub struct Cpu<'a> {
+    pub registers: Registers,
+    /// Number of cycles completed so far.
+    pub cycle: u64,
+    program: &'a [Instruction],
+    pc: usize,
+    /// Cycles already spent on the instruction at `pc`.
+    elapsed: u32,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(program: &'a [Instruction]) -> Cpu<'a> {
+        Cpu {
+            registers: Registers { x: 1 },
+            cycle: 0,
+            program,
+            pc: 0,
+            elapsed: 0,
+        }
+    }
+    /// Advances one cycle. Returns `false` without doing anything once the
+    /// program has finished.
+    pub fn step(&mut self, observer: &mut impl Observer) -> bool {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return false;
+        };
+        self.cycle += 1;
+        observer.during_cycle(self.cycle, &self.registers);
+        self.elapsed += 1;
+        if self.elapsed == instruction.cycles() {
+            instruction.execute(&mut self.registers);
+            self.pc += 1;
+            self.elapsed = 0;
+        }
+        true
+    }
+    /// Steps until the program has finished.
+    pub fn run(&mut self, observer: &mut impl Observer) {
+        while self.step(observer) {}
+    }
+}
+
+/// Sums the signal strength during the 20th cycle and every 40 cycles after.
+pub struct SignalStrength {
+    pub sum: i64,
+}
+
+impl Observer for SignalStrength {
+    fn during_cycle(&mut self, cycle: u64, registers: &Registers) {
+        if cycle % 40 == 20 {
+            self.sum += cycle as i64 * registers.x;
+        }
+    }
+}
+
+/// Draws a pixel per cycle, lit where the 3 pixel wide sprite centred on
+/// register x covers it.
+pub struct Crt {
+    pub screen: Grid<bool>,
+}
+
+impl Crt {
+    pub fn new() -> Crt {
+        Crt {
+            screen: Grid::new(40, 6, false),
+        }
+    }
+    /// The screen as `#` for lit pixels and `.` for dark ones.
+    pub fn render(&self) -> String {
+        render::draw_grid(&self.screen, false, |lit| match lit {
+            true => '#',
+            false => '.',
         })
-        .collect()
+    }
+}
+
+impl Default for Crt {
+    fn default() -> Crt {
+        Crt::new()
+    }
+}
+
+impl Observer for Crt {
+    fn during_cycle(&mut self, cycle: u64, registers: &Registers) {
+        let width = self.screen.width() as u64;
+        let pixel = Coordinate::new(((cycle - 1) % width) as i32, ((cycle - 1) / width) as i32);
+        if let Some(lit) = self.screen.value_at_mut(&pixel) {
+            *lit = (pixel.x as i64).abs_diff(registers.x) <= 1;
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    error::parse_lines(input, |line| line.parse())
 }
 
 #[cfg(test)]
@@ -217,13 +342,35 @@ noop
 noop"
     }
 
+    #[test]
+    fn test_step() {
+        let program = parse("noop\naddx 3\naddx -5").unwrap();
+        let mut cpu = Cpu::new(&program);
+        let mut seen = Vec::new();
+        let mut observer = |cycle, registers: &Registers| seen.push((cycle, registers.x));
+        while cpu.step(&mut observer) {}
+        assert!(!cpu.step(&mut observer));
+        assert_eq!(seen, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(cpu.registers.x, -1);
+        assert_eq!(cpu.cycle, 5);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(input()), 13140);
+        assert_eq!(part1(&parse(input()).unwrap()), 13140);
     }
 
     #[test]
-    fn test_part2() {
-        println!("{}", part2(input()));
+    fn test_crt() {
+        assert_eq!(
+            part2(&parse(input()).unwrap()),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+(unknown glyph at column 1)"
+        );
     }
 }
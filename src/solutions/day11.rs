@@ -8,22 +8,51 @@ lazy_static! {
     static ref OPERATION: Regex = Regex::new(r"(\*|\+) (old|\d+)$").unwrap();
 }
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{Error, Result},
+    params::{Kind, Param, Params},
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rounds1",
+            default: "20",
+            help: "rounds to simulate in part 1",
+            kind: Kind::Unsigned,
+        },
+        Param {
+            name: "rounds2",
+            default: "10000",
+            help: "rounds to simulate in part 2",
+            kind: Kind::Unsigned,
+        },
+    ];
+    type Input = Vec<Monkey>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        part1(input, params.get("rounds1")).into()
+    }
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        part2(input, params.get("rounds2")).into()
+    }
 }
 
-fn part1(input: &str) -> u64 {
-    let monkeys = parse(input);
-    let mut counts = simulate(monkeys, 20, Box::new(|x| x / 3));
+pub fn part1(monkeys: &[Monkey], rounds: usize) -> u64 {
+    let mut counts = simulate(monkeys.to_vec(), rounds, Box::new(|x| x / 3));
     counts.sort();
     counts.into_iter().rev().take(2).product()
 }
 
-fn part2(input: &str) -> u64 {
-    let monkeys = parse(input);
+pub fn part2(monkeys: &[Monkey], rounds: usize) -> u64 {
     let common: u64 = monkeys.iter().map(|m| m.test_value).product();
-    let mut counts = simulate(monkeys, 10000, Box::new(move |x| x % common));
+    let mut counts = simulate(monkeys.to_vec(), rounds, Box::new(move |x| x % common));
     counts.sort();
     counts.into_iter().rev().take(2).product()
 }
@@ -47,36 +76,78 @@ fn simulate(mut monkeys: Vec<Monkey>, n: usize, relief: Box<dyn Fn(u64) -> u64>)
     counts
 }
 
-fn parse(input: &str) -> Vec<Monkey> {
-    input
-        .split("\n\n")
-        .map(|raw_monkey| {
-            let lines = raw_monkey.lines().collect::<Vec<&str>>();
-            let operation_raw = OPERATION.captures(lines[2]).unwrap();
-            Monkey {
-                items: MANY_NUMBERS
-                    .captures_iter(lines[1])
-                    .map(|cap| cap[1].parse().unwrap())
-                    .collect(),
-                operation: (operation_raw[1].to_owned(), operation_raw[2].to_owned()),
-                test_value: TRAILING_NUMBER.captures(lines[3]).unwrap()[1]
-                    .parse()
-                    .unwrap(),
-                true_target: TRAILING_NUMBER.captures(lines[4]).unwrap()[1]
-                    .parse()
-                    .unwrap(),
-                false_target: TRAILING_NUMBER.captures(lines[5]).unwrap()[1]
-                    .parse()
-                    .unwrap(),
+pub fn parse(input: &str) -> Result<Vec<Monkey>> {
+    let mut first_line = 1;
+    let mut monkeys = Vec::new();
+    // Line number and text of each monkey's true and false target.
+    let mut targets = Vec::new();
+    for raw_monkey in input.split("\n\n") {
+        let lines = raw_monkey.lines().collect::<Vec<&str>>();
+        let line = |i: usize| lines.get(i).copied().unwrap_or_default();
+        let error = |i: usize, reason: &str| Error::parse(first_line + i, line(i), reason);
+        let number = |i: usize, reason: &str| -> Result<u64> {
+            TRAILING_NUMBER
+                .captures(line(i))
+                .and_then(|cap| cap[1].parse().ok())
+                .ok_or_else(|| error(i, reason))
+        };
+        let operation_raw = OPERATION
+            .captures(line(2))
+            .ok_or_else(|| error(2, "expected operation"))?;
+        let operator = match &operation_raw[1] {
+            "*" => Operator::Multiply,
+            _ => Operator::Add,
+        };
+        let operand = match &operation_raw[2] {
+            "old" => Operand::Old,
+            value => Operand::Value(value.parse().map_err(|_| error(2, "invalid operand"))?),
+        };
+        for i in [4, 5] {
+            targets.push((first_line + i, line(i).to_owned()));
+        }
+        monkeys.push(Monkey {
+            items: MANY_NUMBERS
+                .captures_iter(line(1))
+                .map(|cap| cap[1].parse().map_err(|_| error(1, "invalid item")))
+                .collect::<Result<_>>()?,
+            operation: (operator, operand),
+            test_value: number(3, "expected divisibility test")?,
+            true_target: number(4, "expected target monkey")? as usize,
+            false_target: number(5, "expected target monkey")? as usize,
+        });
+        first_line += lines.len() + 1;
+    }
+    // Targets can refer to monkeys further down, so they are only checked
+    // once every monkey is known.
+    for (monkey, target_lines) in monkeys.iter().zip(targets.chunks(2)) {
+        for (target, (line, text)) in [monkey.true_target, monkey.false_target]
+            .into_iter()
+            .zip(target_lines)
+        {
+            if target >= monkeys.len() {
+                return Err(Error::parse(*line, text, format!("no monkey {}", target)));
             }
-        })
-        .collect()
+        }
+    }
+    Ok(monkeys)
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Old,
+    Value(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: VecDeque<u64>,
-    operation: (String, String),
+    operation: (Operator, Operand),
     test_value: u64,
     true_target: usize,
     false_target: usize,
@@ -84,18 +155,17 @@ struct Monkey {
 
 impl Monkey {
     fn inspect(&mut self, worry: u64) -> u64 {
-        let operation_operand = match self.operation.1.as_str() {
-            "old" => worry,
-            _ => self.operation.1.parse().unwrap(),
+        let operation_operand = match self.operation.1 {
+            Operand::Old => worry,
+            Operand::Value(value) => value,
         };
-        match self.operation.0.as_str() {
-            "*" => worry * operation_operand,
-            "+" => worry + operation_operand,
-            _ => panic!("Unexpected operator: {}", self.operation.0),
+        match self.operation.0 {
+            Operator::Multiply => worry * operation_operand,
+            Operator::Add => worry + operation_operand,
         }
     }
     fn calculate_target(&mut self, worry: u64) -> usize {
-        let test = worry % self.test_value == 0;
+        let test = worry.is_multiple_of(self.test_value);
         match test {
             true => self.true_target,
             false => self.false_target,
@@ -137,13 +207,27 @@ Monkey 3:
     If false: throw to monkey 1"
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let bad_target = input().replacen("throw to monkey 3", "throw to monkey 7", 1);
+        assert_eq!(
+            parse(&bad_target).unwrap_err().to_string(),
+            "line 6: no monkey 7: `    If false: throw to monkey 7`"
+        );
+        let bad_operand = input().replacen("old + 6", "old + 99999999999999999999", 1);
+        assert!(parse(&bad_operand)
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 10: invalid operand"));
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(10605, part1(input()));
+        assert_eq!(10605, part1(&parse(input()).unwrap(), 20));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(2713310158, part2(input()));
+        assert_eq!(2713310158, part2(&parse(input()).unwrap(), 10000));
     }
 }
@@ -1,50 +1,57 @@
-use std::collections::{HashSet, VecDeque};
+use super::{Answer, Solution};
+use crate::{
+    error::Result,
+    params::Params,
+    utils::{grid::Grid, pathfinding},
+};
 
-use crate::utils::{coordinate::Coordinate, grid::Grid};
+pub struct Day12;
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Grid<char>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grid::parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::<char>::parse(input);
-    let start = grid.find_first('E').unwrap();
-    traverse(start, 'S', &grid).unwrap()
+pub fn part1(grid: &Grid<char>) -> usize {
+    shortest_descent(grid, 'S').unwrap()
 }
 
-fn part2(input: &str) -> usize {
-    let grid = Grid::<char>::parse(input);
-    let start = grid.find_first('E').unwrap();
-    traverse(start, 'a', &grid).unwrap()
+pub fn part2(grid: &Grid<char>) -> usize {
+    shortest_descent(grid, 'a').unwrap()
 }
 
-fn traverse<'a>(start: &'a Coordinate, target: char, grid: &'a Grid<char>) -> Option<usize> {
-    let mut to_visit: VecDeque<(&Coordinate, usize)> = vec![(start, 0)].into_iter().collect();
-    let mut visited: HashSet<&Coordinate> = HashSet::new();
-    while let Some((current, len)) = to_visit.pop_front() {
-        if visited.contains(current) {
-            continue;
-        }
-        if *grid.value_at(current).unwrap() == target {
-            return Some(len);
-        }
-        visited.insert(current);
-        for neighbour in grid.adjacent(current) {
-            if height(grid.value_at(current)) as i8 - height(grid.value_at(neighbour)) as i8 > 1 {
-                continue;
-            }
-            to_visit.push_back((neighbour, len + 1));
-        }
-    }
-    None
+/// Steps from the summit down to the nearest `target`, walking the hiking
+/// rules backwards so every start square can be found in a single search.
+fn shortest_descent(grid: &Grid<char>, target: char) -> Option<usize> {
+    let start = grid.find_first('E')?;
+    let path = pathfinding::bfs(
+        [start],
+        |current| {
+            let from = height(*grid.value_at(current).unwrap());
+            grid.adjacent(current)
+                .into_iter()
+                .filter(move |(_, to)| from as i8 - height(**to) as i8 <= 1)
+                .map(|(neighbour, _)| neighbour)
+        },
+        |current| grid.value_at(current) == Some(&target),
+    )?;
+    Some(path.cost)
 }
 
-fn height(value: Option<&char>) -> char {
-    match value.unwrap() {
+fn height(value: char) -> char {
+    match value {
         'S' => 'a',
         'E' => 'z',
-        _ => *value.unwrap(),
+        _ => value,
     }
 }
 
@@ -59,7 +66,7 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-        assert_eq!(part1(input), 31);
+        assert_eq!(part1(&Grid::parse(input).unwrap()), 31);
     }
 
     #[test]
@@ -69,6 +76,6 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-        assert_eq!(part2(input), 29);
+        assert_eq!(part2(&Grid::parse(input).unwrap()), 29);
     }
 }
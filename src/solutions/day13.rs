@@ -1,156 +1,340 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt, slice, str::FromStr};
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{Error, Result},
+    params::Params,
+};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<(Packet, Packet)>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    parse(input)
-        .chunks(2)
+pub fn part1(pairs: &[(Packet, Packet)]) -> usize {
+    pairs
+        .iter()
         .enumerate()
-        .filter(|(_, packets)| packets[0] < packets[1])
+        .filter(|(_, (left, right))| left < right)
         .map(|(i, _)| i + 1)
         .sum()
 }
 
-fn part2(input: &str) -> usize {
-    let mut packets = parse(input);
-    packets.append(&mut vec![
-        Value::List("[[2]]".to_owned()),
-        Value::List("[[6]]".to_owned()),
-    ]);
+pub fn part2(pairs: &[(Packet, Packet)]) -> usize {
+    let dividers = [divider(2), divider(6)];
+    let mut packets = pairs
+        .iter()
+        .flat_map(|(left, right)| [left.clone(), right.clone()])
+        .collect::<Vec<_>>();
+    packets.extend(dividers.iter().cloned());
     packets.sort();
     packets
         .into_iter()
         .enumerate()
-        .filter(|(_, p)| p.is_separator())
+        .filter(|(_, p)| dividers.contains(p))
         .map(|(i, _)| i + 1)
         .product()
 }
 
-fn parse(input: &str) -> Vec<Value> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| Value::List(line.to_owned()))
-        .collect()
+/// What decided the comparison of two packets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// Indices leading from the outermost lists to the deciding element.
+    /// An integer wrapped into a list counts as that list's item 0.
+    pub path: Vec<usize>,
+    /// The values compared at `path`.
+    pub left: Packet,
+    pub right: Packet,
+    pub rule: Rule,
+    /// Whether an integer had to be wrapped in a list on the way to `path`.
+    pub promoted: bool,
+    pub ordering: Ordering,
 }
 
-impl Ord for Value {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self {
-            Value::Integer(l) => match other {
-                Value::Integer(r) => l.cmp(r),
-                Value::List(_) => Value::List(format!("[{}]", l)).cmp(other),
-            },
-            Value::List(_) => match other {
-                Value::Integer(r) => self.cmp(&Value::List(format!("[{}]", r))),
-                Value::List(_) => {
-                    let l_iter = self.iter();
-                    let mut r_iter = other.iter();
-                    for l in l_iter {
-                        let result = match r_iter.next() {
-                            Some(r) => l.cmp(&r),
-                            None => return Ordering::Greater,
-                        };
-                        if let Ordering::Equal = result {
-                            continue;
-                        }
-                        return result;
-                    }
-                    if r_iter.next().is_some() {
-                        return Ordering::Less;
-                    }
-                    Ordering::Equal
-                }
-            },
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Two integers differ.
+    Integers,
+    /// The left list ran out of items first.
+    LeftRanOut,
+    /// The right list ran out of items first.
+    RightRanOut,
+    /// Nothing differs.
+    Equal,
+}
+
+/// Compares two packets like `Ord` does, reporting where and why the
+/// comparison was decided.
+pub fn explain(left: &Packet, right: &Packet) -> Trace {
+    let mut path = Vec::new();
+    let decision = decide(left, right, &mut path);
+    Trace {
+        path,
+        left: decision.left.clone(),
+        right: decision.right.clone(),
+        rule: decision.rule,
+        promoted: decision.promoted,
+        ordering: decision.ordering,
+    }
+}
+
+struct Decision<'a> {
+    ordering: Ordering,
+    rule: Rule,
+    left: &'a Packet,
+    right: &'a Packet,
+    promoted: bool,
+}
+
+fn decide<'a>(left: &'a Packet, right: &'a Packet, path: &mut Vec<usize>) -> Decision<'a> {
+    let (l, r, promoted) = match (left, right) {
+        (Packet::Integer(l), Packet::Integer(r)) => {
+            let ordering = l.cmp(r);
+            return Decision {
+                ordering,
+                rule: match ordering {
+                    Ordering::Equal => Rule::Equal,
+                    _ => Rule::Integers,
+                },
+                left,
+                right,
+                promoted: false,
+            };
         }
+        (Packet::List(l), Packet::List(r)) => (l.as_slice(), r.as_slice(), false),
+        (Packet::Integer(_), Packet::List(r)) => (slice::from_ref(left), r.as_slice(), true),
+        (Packet::List(l), Packet::Integer(_)) => (l.as_slice(), slice::from_ref(right), true),
+    };
+    let (ordering, rule) = match decide_items(l, r, path) {
+        Some(decision) => {
+            return Decision {
+                promoted: promoted || decision.promoted,
+                ..decision
+            }
+        }
+        None => match l.len().cmp(&r.len()) {
+            Ordering::Less => (Ordering::Less, Rule::LeftRanOut),
+            Ordering::Greater => (Ordering::Greater, Rule::RightRanOut),
+            Ordering::Equal => (Ordering::Equal, Rule::Equal),
+        },
+    };
+    Decision {
+        ordering,
+        rule,
+        left,
+        right,
+        promoted,
     }
 }
 
-impl PartialOrd for Value {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// The first pair of items that differ, if any.
+fn decide_items<'a>(
+    left: &'a [Packet],
+    right: &'a [Packet],
+    path: &mut Vec<usize>,
+) -> Option<Decision<'a>> {
+    for (i, (l, r)) in left.iter().zip(right).enumerate() {
+        path.push(i);
+        let decision = decide(l, r, path);
+        if decision.ordering != Ordering::Equal {
+            return Some(decision);
+        }
+        path.pop();
     }
+    None
 }
 
-#[derive(Eq, PartialEq, Debug)]
-enum Value {
-    Integer(u32),
-    List(String),
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let order = match self.ordering {
+            Ordering::Less => "in the right order",
+            Ordering::Equal => "equal",
+            Ordering::Greater => "not in the right order",
+        };
+        let rule = match self.rule {
+            Rule::Integers => "integer comparison",
+            Rule::LeftRanOut => "left ran out",
+            Rule::RightRanOut => "right ran out",
+            Rule::Equal => "no difference",
+        };
+        let promotion = match self.promoted {
+            true => " after list promotion",
+            false => "",
+        };
+        write!(
+            f,
+            "{}, {}{} at {:?}: {} vs {}",
+            order, rule, promotion, self.path, self.left, self.right
+        )
+    }
 }
 
-impl Value {
-    fn iter(&self) -> ValueIterator {
-        match self {
-            Value::List(s) => ValueIterator {
-                content: &s[1..s.len() - 1],
-                pos: 0,
-            },
-            _ => panic!("Not a list"),
+/// The divider packet `[[n]]`.
+fn divider(n: u32) -> Packet {
+    Packet::List(vec![Packet::List(vec![Packet::Integer(n)])])
+}
+
+/// Pairs of packets on consecutive lines, separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>> {
+    let mut pairs = Vec::new();
+    // The first packet of the pair being read, with its line number and text.
+    let mut left: Option<(usize, &str, Packet)> = None;
+    let mut pair_ended = false;
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            if let Some((n, text, _)) = left {
+                return Err(Error::parse(n, text, "packet without a pair"));
+            }
+            pair_ended = false;
+            continue;
+        }
+        if pair_ended {
+            return Err(Error::parse(
+                i + 1,
+                line,
+                "expected a blank line between pairs",
+            ));
+        }
+        let packet = line.parse().map_err(|e| Error::parse(i + 1, line, e))?;
+        match left.take() {
+            None => left = Some((i + 1, line, packet)),
+            Some((_, _, first)) => {
+                pairs.push((first, packet));
+                pair_ended = true;
+            }
         }
     }
-    fn is_separator(&self) -> bool {
-        match self {
-            Value::List(c) => c == "[[2]]" || c == "[[6]]",
-            _ => false,
+    match left {
+        Some((n, text, _)) => Err(Error::parse(n, text, "packet without a pair")),
+        None => Ok(pairs),
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Packet {
+    Integer(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Integer(l), Packet::Integer(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            (Packet::Integer(_), Packet::List(r)) => slice::from_ref(self).cmp(r),
+            (Packet::List(l), Packet::Integer(_)) => l.as_slice().cmp(slice::from_ref(other)),
         }
     }
 }
 
-struct ValueIterator<'a> {
-    content: &'a str,
-    pos: usize,
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-impl<'a> Iterator for ValueIterator<'a> {
-    type Item = Value;
-    fn next(&mut self) -> Option<Self::Item> {
-        let iter = self.content.chars().enumerate().skip(self.pos);
-        for (i, c) in iter {
-            let (len, v) = match c {
-                '[' => match find_matching(self.content, self.pos) {
-                    Some(end) => (
-                        end + 1 - self.pos,
-                        Some(Value::List(String::from(&self.content[i..end + 1]))),
-                    ),
-                    None => panic!("No matching end bracket found"),
-                },
-                ',' => (1, None),
-                c if c.is_numeric() => {
-                    let number: u32 = match self.content[self.pos..self.content.len()].find(',') {
-                        Some(i) => self.content[self.pos..self.pos + i].parse().unwrap(),
-                        None => self.content[self.pos..self.content.len()].parse().unwrap(),
-                    };
-                    (number.to_string().len(), Some(Value::Integer(number)))
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::Integer(n) => write!(f, "{}", n),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
                 }
-                _ => panic!("Unexpected char"),
-            };
-            self.pos += len;
-            if let Some(v) = v {
-                return Some(v);
+                write!(f, "]")
             }
         }
-        None
     }
 }
 
-fn find_matching(input: &str, start: usize) -> Option<usize> {
-    let mut count = 0;
-    for (i, c) in input.chars().enumerate().skip(start) {
-        match c {
-            '[' => count += 1,
-            ']' => {
-                count -= 1;
-                if count == 0 {
-                    return Some(i);
+impl FromStr for Packet {
+    type Err = String;
+    fn from_str(s: &str) -> std::result::Result<Packet, String> {
+        let mut parser = PacketParser {
+            chars: s.chars().collect(),
+            pos: 0,
+        };
+        if parser.peek() != Some('[') {
+            return Err(parser.error("expected a bracketed list"));
+        }
+        let packet = parser.packet()?;
+        match parser.peek() {
+            None => Ok(packet),
+            Some(c) => Err(parser.error(format!("unexpected {:?} after the packet", c))),
+        }
+    }
+}
+
+/// Recursive descent over the chars of a single packet.
+struct PacketParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PacketParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+    fn error(&self, reason: impl Into<String>) -> String {
+        format!("{} at column {}", reason.into(), self.pos + 1)
+    }
+    fn packet(&mut self) -> std::result::Result<Packet, String> {
+        match self.peek() {
+            Some('[') => self.list(),
+            Some(c) if c.is_ascii_digit() => self.integer(),
+            Some(c) => Err(self.error(format!("unexpected {:?}", c))),
+            None => Err(self.error("unexpected end of packet")),
+        }
+    }
+    fn list(&mut self) -> std::result::Result<Packet, String> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Packet::List(items));
+        }
+        loop {
+            items.push(self.packet()?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Packet::List(items));
                 }
+                Some(c) => return Err(self.error(format!("expected ',' or ']', found {:?}", c))),
+                None => return Err(self.error("unclosed list")),
+            }
+        }
+    }
+    fn integer(&mut self) -> std::result::Result<Packet, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits = self.chars[start..self.pos].iter().collect::<String>();
+        match digits.parse() {
+            Ok(n) => Ok(Packet::Integer(n)),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error(format!("integer {} is too large", digits)))
             }
-            _ => {}
         }
     }
-    None
 }
 
 #[cfg(test)]
@@ -158,7 +342,7 @@ mod tests {
     use super::*;
 
     fn input() -> &'static str {
-        return "[1,1,3,1,1]
+        "[1,1,3,1,1]
 [1,1,5,1,1]
 
 [[1],[2,3,4]]
@@ -180,16 +364,92 @@ mod tests {
 [[]]
 
 [1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
+[1,[2,[3,[4,[5,6,0]]]],8,9]"
+    }
+
+    #[test]
+    fn test_parse() {
+        for line in input().lines().filter(|line| !line.is_empty()) {
+            assert_eq!(line.parse::<Packet>().unwrap().to_string(), line);
+        }
+        assert_eq!(
+            "[1,[2,]]".parse::<Packet>(),
+            Err("unexpected ']' at column 7".to_owned())
+        );
+        assert_eq!(
+            "[1,2".parse::<Packet>(),
+            Err("unclosed list at column 5".to_owned())
+        );
+        assert_eq!(
+            "[1]]".parse::<Packet>(),
+            Err("unexpected ']' after the packet at column 4".to_owned())
+        );
+        assert!("1".parse::<Packet>().is_err());
+        assert_eq!(
+            parse("[1]\n[2]\n\n[3]\n").unwrap_err().to_string(),
+            "line 4: packet without a pair: `[3]`"
+        );
+        assert_eq!(
+            parse("[1]\n\n[2]\n[3]").unwrap_err().to_string(),
+            "line 1: packet without a pair: `[1]`"
+        );
+        assert_eq!(
+            parse("[1]\n[2]\n[3]\n[4]").unwrap_err().to_string(),
+            "line 3: expected a blank line between pairs: `[3]`"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let pairs = parse(input()).unwrap();
+        let traces = pairs
+            .iter()
+            .map(|(left, right)| explain(left, right))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            traces.iter().map(|t| t.rule).collect::<Vec<_>>(),
+            vec![
+                Rule::Integers,
+                Rule::Integers,
+                Rule::Integers,
+                Rule::LeftRanOut,
+                Rule::RightRanOut,
+                Rule::LeftRanOut,
+                Rule::RightRanOut,
+                Rule::Integers,
+            ]
+        );
+        assert_eq!(traces[0].path, vec![2]);
+        assert_eq!(traces[1].path, vec![1, 0]);
+        assert_eq!(traces[1].left.to_string(), "2");
+        assert_eq!(traces[1].right.to_string(), "4");
+        assert!(traces[1].promoted && !traces[0].promoted);
+        assert_eq!(traces[7].path, vec![1, 1, 1, 1, 2]);
+        assert_eq!(
+            traces[2].to_string(),
+            "not in the right order, integer comparison after list promotion at [0, 0]: 9 vs 8"
+        );
+        let trace = explain(&"[7]".parse().unwrap(), &"[[[9]],[1]]".parse().unwrap());
+        assert_eq!(trace.path, vec![0, 0, 0]);
+        assert_eq!(
+            (trace.left.to_string(), trace.right.to_string()),
+            ("7".to_owned(), "9".to_owned())
+        );
+        let trace = explain(&"[[]]".parse().unwrap(), &"[3]".parse().unwrap());
+        assert_eq!((trace.rule, trace.promoted), (Rule::LeftRanOut, true));
+        assert_eq!(trace.right.to_string(), "3");
+        for (left, right) in &pairs {
+            assert_eq!(explain(left, right).ordering, left.cmp(right));
+        }
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(input()), 13);
+        assert_eq!(part1(&parse(input()).unwrap()), 13);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(input()), 140);
+        assert_eq!(part2(&parse(input()).unwrap()), 140);
     }
 }
@@ -1,95 +1,124 @@
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::{cmp, collections::HashSet, iter};
+use std::collections::HashSet;
 
-use crate::utils::coordinate::Coordinate;
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+    utils::{
+        coordinate::{Coordinate, Neighbourhood},
+        grid::Grid,
+    },
+};
 
 lazy_static! {
     static ref NUMBER_PAIRS: Regex = Regex::new(r"(\d+),(\d+)").unwrap();
 }
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = HashSet<Coordinate>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let mut obstacles = parse(input);
-    let rock_count = obstacles.len();
-    let y_max = obstacles.iter().map(|c| c.y).max().unwrap();
-    simulate_flow(&mut obstacles, y_max, false);
-    obstacles.len() - rock_count
+pub fn part1(rock: &HashSet<Coordinate>) -> usize {
+    let y_max = rock.iter().map(|c| c.y).max().unwrap();
+    let (mut cave, source) = cave(rock, y_max);
+    simulate_flow(&mut cave, source, false)
 }
 
-fn part2(input: &str) -> usize {
-    let mut obstacles = parse(input);
-    let rock_count = obstacles.len();
-    let y_max = obstacles.iter().map(|c| c.y).max().unwrap();
-    simulate_flow(&mut obstacles, y_max + 2 - 1, true);
-    obstacles.len() - rock_count
+pub fn part2(rock: &HashSet<Coordinate>) -> usize {
+    let y_max = rock.iter().map(|c| c.y).max().unwrap();
+    let (mut cave, source) = cave(rock, y_max + 2 - 1);
+    simulate_flow(&mut cave, source, true)
 }
 
-fn simulate_flow(obstacles: &mut HashSet<Coordinate>, y_max: i32, solid_floor: bool) {
-    let flow = iter::repeat(Coordinate::new(500, 0));
-    for sand in flow {
-        if obstacles.contains(&sand) {
-            return;
-        }
-        let new_pos = fall(sand, obstacles, y_max);
+/// The cave down to `y_max` with rock marked as blocked, and where the sand
+/// source lies in it. Sand can drift at most one column sideways per row,
+/// so the cave spans every column sand can reach, shifted so that the
+/// leftmost one is at `x = 0`.
+fn cave(rock: &HashSet<Coordinate>, y_max: i32) -> (Grid<bool>, Coordinate) {
+    let x_min = rock.iter().map(|c| c.x).min().unwrap().min(500 - y_max) - 1;
+    let x_max = rock.iter().map(|c| c.x).max().unwrap().max(500 + y_max) + 1;
+    let offset = Coordinate::new(x_min, 0);
+    let mut cave = Grid::new((x_max - x_min) as usize + 1, y_max as usize + 1, false);
+    rock.iter().for_each(|c| cave[*c - offset] = true);
+    (cave, Coordinate::new(500, 0) - offset)
+}
+
+/// Drops sand until it comes to rest at the source or, without a solid
+/// floor, falls to the bottom. Returns the number of units at rest.
+fn simulate_flow(cave: &mut Grid<bool>, source: Coordinate, solid_floor: bool) -> usize {
+    let y_max = cave.height() as i32 - 1;
+    let mut count = 0;
+    while !cave[source] {
+        let new_pos = fall(source, cave);
         if !solid_floor && new_pos.y == y_max {
-            return;
+            break;
         }
-        obstacles.insert(new_pos);
+        cave[new_pos] = true;
+        count += 1;
     }
+    count
 }
 
-fn fall(sand: Coordinate, obstacles: &HashSet<Coordinate>, y_max: i32) -> Coordinate {
-    for dx in &[0, -1, 1] {
-        let to_test = Coordinate::new(sand.x + dx, sand.y + 1);
-        if !obstacles.contains(&to_test) && to_test.y <= y_max {
-            return fall(to_test, obstacles, y_max);
+fn fall(sand: Coordinate, cave: &Grid<bool>) -> Coordinate {
+    let below = Neighbourhood::Custom(&[(0, 1), (-1, 1), (1, 1)]);
+    for to_test in sand.neighbours(below) {
+        if cave.value_at(&to_test) == Some(&false) {
+            return fall(to_test, cave);
         }
     }
     sand
 }
 
-fn parse(input: &str) -> HashSet<Coordinate> {
-    input.lines().flat_map(parse_obstacle).collect()
+pub fn parse(input: &str) -> Result<HashSet<Coordinate>> {
+    Ok(error::parse_lines(input, parse_obstacle)?
+        .into_iter()
+        .flatten()
+        .collect())
 }
 
-fn parse_obstacle(line: &str) -> HashSet<Coordinate> {
+fn parse_obstacle(line: &str) -> std::result::Result<HashSet<Coordinate>, String> {
     let mut rock: HashSet<Coordinate> = HashSet::new();
     let all_pairs = NUMBER_PAIRS
         .captures_iter(line)
-        .map(|cap| {
-            (
-                cap[1].parse::<i32>().unwrap(),
-                cap[2].parse::<i32>().unwrap(),
-            )
+        .map(|cap| match (cap[1].parse::<i32>(), cap[2].parse::<i32>()) {
+            (Ok(x), Ok(y)) => Ok(Coordinate::new(x, y)),
+            _ => Err(format!("invalid coordinate {:?}", &cap[0])),
         })
-        .collect::<Vec<_>>();
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if all_pairs.is_empty() {
+        return Err("expected `x,y -> x,y`".to_owned());
+    }
     let mut all_pairs = all_pairs.windows(2);
     while let Some([left, right]) = all_pairs.next() {
-        let x_range: Vec<_> = match left.0 == right.0 {
-            true => vec![left.0; (left.1.abs_diff(right.1) + 1) as usize]
-                .into_iter()
-                .collect(),
-            false => (cmp::min(left.0, right.0)..cmp::max(left.0, right.0) + 1).collect(),
-        };
-        let y_range: Vec<_> = match left.1 == right.1 {
-            true => vec![left.1; (left.0.abs_diff(right.0) + 1) as usize]
-                .into_iter()
-                .collect(),
-            false => (cmp::min(left.1, right.1)..cmp::max(left.1, right.1) + 1).collect(),
-        };
-        x_range
-            .into_iter()
-            .zip(y_range.into_iter())
-            .for_each(|(x, y)| {
-                rock.insert(Coordinate::new(x, y));
-            })
+        if left.x != right.x && left.y != right.y {
+            return Err(format!(
+                "diagonal path from {},{} to {},{}",
+                left.x, left.y, right.x, right.y
+            ));
+        }
+        let step = (*right - *left).signum();
+        let mut current = *left;
+        rock.insert(current);
+        while current != *right {
+            current += step;
+            rock.insert(current);
+        }
     }
-    rock
+    Ok(rock)
 }
 
 #[cfg(test)]
@@ -100,13 +129,24 @@ mod tests {
     fn test_part1() {
         let input = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
-        assert_eq!(part1(input), 24);
+        assert_eq!(part1(&parse(input).unwrap()), 24);
     }
 
     #[test]
     fn test_part2() {
         let input = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
-        assert_eq!(part2(input), 93);
+        assert_eq!(part2(&parse(input).unwrap()), 93);
+    }
+
+    #[test]
+    fn test_deep_cave() {
+        // Deep enough for sand to drift left of x = 0.
+        let rock = parse("0,600 -> 2,600").unwrap();
+        let (cave, source) = cave(&rock, 601);
+        assert_eq!(source, Coordinate::new(602, 0));
+        assert_eq!(cave.width(), 602 + 601 + 2);
+        assert!(cave[Coordinate::new(102, 600)]);
+        assert_eq!(part1(&rock), 0);
     }
 }
@@ -1,107 +1,123 @@
-use std::ops::Range;
-
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::collections::HashSet;
 
-use crate::utils::coordinate::Coordinate;
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::{Kind, Param, Params},
+    utils::{coordinate::Coordinate64, interval::IntervalSet},
+};
 
 lazy_static! {
     static ref COORDINATES: Regex =
         Regex::new(r"x=(\-?\d+), y=(\-?\d+).*x=(\-?\d+), y=(\-?\d+)$").unwrap();
 }
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input, 2000000));
-    println!("Part 2: {}", part2(input, 4000000).unwrap());
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "row",
+            default: "2000000",
+            help: "row to count covered positions in for part 1",
+            kind: Kind::Integer,
+        },
+        Param {
+            name: "max",
+            default: "4000000",
+            help: "largest x and y the distress beacon can have in part 2",
+            kind: Kind::Unsigned,
+        },
+    ];
+    type Input = Vec<Sensor>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        part1(input, params.get("row")).into()
+    }
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        part2(input, params.get("max")).unwrap().into()
+    }
 }
 
-fn part1(input: &str, target_y: i32) -> u32 {
-    let sensors = parse(input);
-    let y_coverage = row_coverage(&sensors, target_y, None);
-    let y_coverage = merge_ranges(y_coverage);
-    y_coverage.iter().map(|r| r.start.abs_diff(r.end) - 1).sum()
+/// Positions on the row where no beacon can be: those within reach of a
+/// sensor, less the known beacons.
+pub fn part1(sensors: &[Sensor], target_y: i64) -> u64 {
+    let y_coverage = row_coverage(sensors, target_y);
+    let beacons = sensors
+        .iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y == target_y && y_coverage.contains(beacon.x))
+        .collect::<HashSet<_>>();
+    y_coverage.len() as u64 - beacons.len() as u64
 }
 
-fn part2(input: &str, max: i32) -> Option<i64> {
-    let sensors = parse(input);
-    for y in 0i32..max {
-        let y_coverage = row_coverage(&sensors, y, Some(max));
-        let y_coverage = merge_ranges(y_coverage);
-        if y_coverage.len() == 1 {
-            continue;
+pub fn part2(sensors: &[Sensor], max: i64) -> Option<i64> {
+    for y in 0..=max {
+        // The first x from 0 not covered, found by following the sorted
+        // ranges until one starts after it.
+        let mut free_x = 0;
+        for range in row_coverage(sensors, y).iter() {
+            if range.start > free_x {
+                break;
+            }
+            free_x = free_x.max(range.end);
+        }
+        if free_x <= max {
+            return Some(free_x * 4000000 + y);
         }
-        return Some(y_coverage.first().unwrap().end as i64 * 4000000 + y as i64);
     }
     None
 }
 
-fn merge_ranges(mut ranges: Vec<Range<i32>>) -> Vec<Range<i32>> {
-    ranges.sort_by(|a, b| a.start.cmp(&b.start));
-    ranges
-        .into_iter()
-        .fold(Vec::<Range<i32>>::new(), |mut acc, range| {
-            match acc.pop() {
-                Some(prev_range) => {
-                    if prev_range.end >= range.start {
-                        acc.push(prev_range.start..prev_range.end.max(range.end));
-                    } else {
-                        acc.push(prev_range.start..prev_range.end);
-                        acc.push(range.start..range.end);
-                    }
-                }
-                None => {
-                    acc.push(range.start..range.end);
-                }
-            }
-            acc
-        })
-}
-
-fn row_coverage(sensors: &Vec<Sensor>, target_y: i32, x_cap: Option<i32>) -> Vec<Range<i32>> {
-    let mut covered: Vec<Range<i32>> = Vec::new();
-    for sensor in sensors {
-        let centre_distance = sensor.pos.y.abs_diff(target_y);
-        let current_distance = match sensor.distance.checked_sub(centre_distance) {
-            Some(d) => d,
-            None => continue,
-        };
-        covered.push(
-            (sensor.pos.x - current_distance as i32)..match x_cap {
-                Some(cap) => cap.min(sensor.pos.x + current_distance as i32) + 1,
-                None => sensor.pos.x + current_distance as i32 + 1,
-            },
-        );
-    }
-    covered
-}
-
-fn parse(input: &str) -> Vec<Sensor> {
-    input
-        .lines()
-        .map(|line| {
-            let cap = COORDINATES.captures(line).unwrap();
-            Sensor::new(
-                Coordinate::new(cap[1].parse().unwrap(), cap[2].parse().unwrap()),
-                &Coordinate::new(cap[3].parse().unwrap(), cap[4].parse().unwrap()),
-            )
+fn row_coverage(sensors: &[Sensor], target_y: i64) -> IntervalSet<i64> {
+    sensors
+        .iter()
+        .filter_map(|sensor| {
+            let centre_distance = sensor.pos.y.abs_diff(target_y);
+            let reach = sensor.distance.checked_sub(centre_distance)? as i64;
+            Some(sensor.pos.x - reach..sensor.pos.x + reach + 1)
         })
         .collect()
 }
 
-fn manhattan(c1: &Coordinate, c2: &Coordinate) -> u32 {
-    (c1.x - c2.x).unsigned_abs() + (c1.y - c2.y).unsigned_abs()
+pub fn parse(input: &str) -> Result<Vec<Sensor>> {
+    error::parse_lines(input, |line| {
+        let cap = COORDINATES
+            .captures(line)
+            .ok_or("expected sensor and beacon coordinates")?;
+        let n = |i: usize| {
+            cap[i]
+                .parse::<i64>()
+                .map_err(|_| format!("invalid coordinate {:?}", &cap[i]))
+        };
+        Ok(Sensor::new(
+            Coordinate64::new(n(1)?, n(2)?),
+            &Coordinate64::new(n(3)?, n(4)?),
+        ))
+    })
 }
 
 #[derive(Debug)]
-struct Sensor {
-    pos: Coordinate,
-    distance: u32,
+pub struct Sensor {
+    pub pos: Coordinate64,
+    /// The nearest beacon.
+    pub beacon: Coordinate64,
+    pub distance: u64,
 }
 
 impl Sensor {
-    pub fn new(pos: Coordinate, nearest: &Coordinate) -> Sensor {
-        let distance = manhattan(&pos, nearest);
-        Sensor { pos, distance }
+    pub fn new(pos: Coordinate64, nearest: &Coordinate64) -> Sensor {
+        let distance = pos.manhattan(nearest);
+        Sensor {
+            pos,
+            beacon: *nearest,
+            distance,
+        }
     }
 }
 
@@ -128,11 +144,17 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3"
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(input(), 10), 26);
+        let sensors = parse(input()).unwrap();
+        assert_eq!(part1(&sensors, 10), 26);
+        // Other rows, checked against brute force.
+        assert_eq!(part1(&sensors, -5), 14);
+        assert_eq!(part1(&sensors, 0), 35);
+        assert_eq!(part1(&sensors, 22), 20);
+        assert_eq!(part1(&sensors, 25), 4);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(input(), 20), Some(56000011));
+        assert_eq!(part2(&parse(input()).unwrap(), 20), Some(56000011));
     }
 }
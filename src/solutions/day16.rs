@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -10,27 +8,58 @@ lazy_static! {
     .unwrap();
 }
 
-pub fn run(input: &str) {
-    use std::time::Instant;
-    let mut now = Instant::now();
-    println!("Part 1: {} - {:.2?}", part1(input), now.elapsed());
-    now = Instant::now();
-    println!("Part 2: {} - {:.2?}", part2(input), now.elapsed());
+use super::{Answer, Solution};
+use crate::{
+    error::{Error, Result},
+    params::{Kind, Param, Params},
+    utils::graph::{Edge, Graph},
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "minutes1",
+            default: "30",
+            help: "minutes before the eruption in part 1",
+            kind: Kind::Unsigned,
+        },
+        Param {
+            name: "minutes2",
+            default: "26",
+            help: "minutes left after teaching the elephant in part 2",
+            kind: Kind::Unsigned,
+        },
+    ];
+    type Input = Valves;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        part1(input, params.get("minutes1")).into()
+    }
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        part2(input, params.get("minutes2")).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let (ids, nodes, edges) = parse(input);
-    let optimal = optimal_valves(&nodes, &edges, 30);
-    solve(ids["AA"], &nodes, &edges, &optimal, [30, 0])
+/// The start valve, flow rate of each valve and the cost of moving to and
+/// opening each other valve, for AA and the valves with a flow rate.
+pub type Valves = (usize, Vec<usize>, Vec<Vec<Edge>>);
+
+pub fn part1((start, nodes, edges): &Valves, minutes: usize) -> usize {
+    let optimal = optimal_valves(nodes, edges, minutes);
+    solve(*start, nodes, edges, &optimal, [minutes, 0])
 }
 
-fn part2(input: &str) -> usize {
-    let (ids, nodes, edges) = parse(input);
-    let optimal = optimal_valves(&nodes, &edges, 26);
-    solve(ids["AA"], &nodes, &edges, &optimal, [26, 26])
+pub fn part2((start, nodes, edges): &Valves, minutes: usize) -> usize {
+    let optimal = optimal_valves(nodes, edges, minutes);
+    solve(*start, nodes, edges, &optimal, [minutes, minutes])
 }
 
-fn solve(
+pub fn solve(
     start: usize,
     nodes: &[usize],
     edges: &[Vec<Edge>],
@@ -109,7 +138,7 @@ fn best_case_pressure(state: &State, optimal: &[Vec<(usize, usize, usize)>]) ->
     best
 }
 
-fn optimal_valves(
+pub fn optimal_valves(
     nodes: &[usize],
     edges: &[Vec<Edge>],
     max_minutes: usize,
@@ -139,63 +168,58 @@ fn optimal_valves(
         .collect::<Vec<_>>()
 }
 
-fn parse(input: &str) -> (HashMap<String, usize>, Vec<usize>, Vec<Vec<Edge>>) {
-    let mut ids: HashMap<String, usize> = HashMap::new();
-    let mut valves: Vec<(usize, Vec<String>)> = Vec::new();
-    for line in input.lines() {
-        let captures = VALVE.captures(line).unwrap();
-        ids.insert(captures[1].to_owned(), valves.len());
-        valves.push((
-            captures[2].parse().unwrap(),
-            captures[3]
-                .split(',')
-                .map(|n| n.trim().to_owned())
-                .collect(),
-        ));
+pub fn parse(input: &str) -> Result<Valves> {
+    let mut graph: Graph<usize> = Graph::new();
+    let mut tunnels: Vec<(usize, usize, Vec<String>)> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let captures = VALVE
+            .captures(line)
+            .ok_or_else(|| Error::parse(i + 1, line, "expected valve description"))?;
+        let flow = captures[2]
+            .parse()
+            .map_err(|_| Error::parse(i + 1, line, "invalid flow rate"))?;
+        let neighbours = captures[3]
+            .split(',')
+            .map(|n| n.trim().to_owned())
+            .collect();
+        tunnels.push((i, graph.add_node(&captures[1], flow), neighbours));
     }
-    let num_nodes = valves.len();
-    let mut distances = vec![vec![usize::MAX; num_nodes]; num_nodes];
-    for (i, (_, neighbours)) in valves.iter().enumerate() {
-        distances[i][i] = 0;
+    for (i, id, neighbours) in tunnels {
         for n in neighbours {
-            distances[i][ids[n]] = 1;
+            match graph.id(&n) {
+                Some(target) => graph.add_edge(id, target, 1),
+                None => {
+                    let line = input.lines().nth(i).unwrap_or_default();
+                    return Err(Error::parse(i + 1, line, format!("unknown valve {}", n)));
+                }
+            }
         }
     }
-    floyd_warshall(&mut distances, num_nodes);
-    let edges = (0..num_nodes)
+    let start = graph
+        .id("AA")
+        .ok_or_else(|| Error::input("no valve AA to start from"))?;
+    let valves = graph.contract(|id, flow| id == start || *flow > 0);
+    if valves.len() > 64 {
+        return Err(Error::input("more than 64 valves with a flow rate"));
+    }
+    let nodes = valves.nodes().to_vec();
+    // Moving to a valve is only worth it to open it, which takes a minute.
+    let edges = (0..valves.len())
         .map(|from| {
-            let mut from_edges = (0..num_nodes)
-                .flat_map(|to| {
-                    match from != to
-                        && (from == ids["AA"] || valves[from].0 > 0)
-                        && valves[to].0 > 0
-                    {
-                        true => Some(Edge {
-                            target_id: to,
-                            weight: distances[from][to] + 1,
-                        }),
-                        false => None,
-                    }
+            let mut from_edges = valves
+                .edges(from)
+                .iter()
+                .filter(|edge| nodes[edge.target_id] > 0)
+                .map(|edge| Edge {
+                    target_id: edge.target_id,
+                    weight: edge.weight + 1,
                 })
                 .collect::<Vec<_>>();
-            from_edges.sort_by(|a, b| valves[a.target_id].0.cmp(&valves[b.target_id].0).reverse());
+            from_edges.sort_by(|a, b| nodes[a.target_id].cmp(&nodes[b.target_id]).reverse());
             from_edges
         })
         .collect::<Vec<_>>();
-    let nodes = valves.into_iter().map(|(flow, _)| flow).collect::<Vec<_>>();
-    (ids, nodes, edges)
-}
-
-fn floyd_warshall(distances: &mut [Vec<usize>], n: usize) {
-    for k in 0..n {
-        for i in 0..n {
-            for j in 0..n {
-                if distances[i][k].saturating_add(distances[k][j]) < distances[i][j] {
-                    distances[i][j] = distances[i][k] + distances[k][j];
-                }
-            }
-        }
-    }
+    Ok((valves.id("AA").unwrap(), nodes, edges))
 }
 
 #[derive(Debug, Clone)]
@@ -211,12 +235,6 @@ struct ActorState {
     minutes_remaining: usize,
 }
 
-#[derive(Debug)]
-struct Edge {
-    target_id: usize,
-    weight: usize,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,11 +254,11 @@ Valve JJ has flow rate=21; tunnel leads to valve II"
 
     #[test]
     fn test_part1() {
-        assert_eq!(1651, part1(input()));
+        assert_eq!(1651, part1(&parse(input()).unwrap(), 30));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(1707, part2(input()));
+        assert_eq!(1707, part2(&parse(input()).unwrap(), 26));
     }
 }
@@ -1,37 +1,62 @@
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<(u32, u32)>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.split(' ').map(normalize).collect::<Vec<_>>())
-        .map(|moves| round_points(moves[1], moves[0]) + moves[1] + 1)
+pub fn part1(rounds: &[(u32, u32)]) -> u32 {
+    rounds
+        .iter()
+        .map(|(other, slf)| round_points(*slf, *other) + slf + 1)
         .sum()
 }
 
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.split(' ').map(normalize).collect::<Vec<_>>())
-        .map(|line| {
-            match line[1] {
-                0 => (line[0] + 2) % 3 + 1,     // lose
-                1 => 3 + line[0] + 1,           // draw
-                2 => 6 + (line[0] + 1) % 3 + 1, // win
+pub fn part2(rounds: &[(u32, u32)]) -> u32 {
+    rounds
+        .iter()
+        .map(|(other, outcome)| {
+            match outcome {
+                0 => (other + 2) % 3 + 1,     // lose
+                1 => 3 + other + 1,           // draw
+                2 => 6 + (other + 1) % 3 + 1, // win
                 _ => panic!("Invalid state"),
             }
         })
         .sum()
 }
 
-fn normalize(input: &str) -> u32 {
+pub fn parse(input: &str) -> Result<Vec<(u32, u32)>> {
+    error::parse_lines(
+        input,
+        |line| match line.split(' ').collect::<Vec<_>>()[..] {
+            [first, second] => Ok((normalize(first)?, normalize(second)?)),
+            _ => Err("expected two moves".to_owned()),
+        },
+    )
+}
+
+fn normalize(input: &str) -> std::result::Result<u32, String> {
     match input {
-        "A" | "X" => 0,
-        "B" | "Y" => 1,
-        "C" | "Z" => 2,
-        _ => panic!("Invalid state"),
+        "A" | "X" => Ok(0),
+        "B" | "Y" => Ok(1),
+        "C" | "Z" => Ok(2),
+        _ => Err(format!("unexpected move {:?}", input)),
     }
 }
 fn round_points(slf: u32, other: u32) -> u32 {
@@ -52,7 +77,7 @@ mod tests {
         let input = "A Y
 B X
 C Z";
-        assert_eq!(part1(input), 15);
+        assert_eq!(part1(&parse(input).unwrap()), 15);
     }
 
     #[test]
@@ -60,6 +85,6 @@ C Z";
         let input = "A Y
 B X
 C Z";
-        assert_eq!(part2(input), 12);
+        assert_eq!(part2(&parse(input).unwrap()), 12);
     }
 }
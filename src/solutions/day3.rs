@@ -1,22 +1,40 @@
 use std::collections::HashSet;
 
-pub fn run(input: &str) {
-    println!("{}", part1(input));
-    println!("{}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> i32 {
-    input
-        .lines()
+pub fn part1(rucksacks: &[String]) -> i32 {
+    rucksacks
+        .iter()
         .map(|line| line.split_at(line.len() / 2))
         .flat_map(|(first, second)| items_in_all(&[first, second]))
         .map(priority)
         .sum()
 }
 
-fn part2(input: &str) -> i32 {
-    input
-        .lines()
+pub fn part2(rucksacks: &[String]) -> i32 {
+    rucksacks
+        .iter()
+        .map(|line| line.as_str())
         .collect::<Vec<_>>()
         .chunks(3)
         .flat_map(items_in_all)
@@ -24,6 +42,15 @@ fn part2(input: &str) -> i32 {
         .sum()
 }
 
+pub fn parse(input: &str) -> Result<Vec<String>> {
+    error::parse_lines(input, |line| {
+        match line.chars().find(|c| !c.is_ascii_alphabetic()) {
+            Some(c) => Err(format!("unexpected item {:?}", c)),
+            None => Ok(line.to_owned()),
+        }
+    })
+}
+
 fn items_in_all(compartments: &[&str]) -> HashSet<char> {
     assert!(!compartments.is_empty());
     compartments[0]
@@ -58,7 +85,7 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        assert_eq!(part1(input), 157);
+        assert_eq!(part1(&parse(input).unwrap()), 157);
     }
 
     #[test]
@@ -69,6 +96,6 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        assert_eq!(part2(input), 70);
+        assert_eq!(part2(&parse(input).unwrap()), 70);
     }
 }
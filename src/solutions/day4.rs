@@ -1,37 +1,51 @@
-use std::collections::HashSet;
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+    utils::interval::IntervalSet,
+};
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<(IntervalSet<u32>, IntervalSet<u32>)>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.split(',').map(to_range).collect::<Vec<_>>())
-        .filter(|x| overlaps(x, true))
-        .count()
+pub fn part1(pairs: &[(IntervalSet<u32>, IntervalSet<u32>)]) -> usize {
+    pairs.iter().filter(|x| overlaps(x, true)).count()
 }
 
-fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.split(',').map(to_range).collect::<Vec<_>>())
-        .filter(|x| overlaps(x, false))
-        .count()
+pub fn part2(pairs: &[(IntervalSet<u32>, IntervalSet<u32>)]) -> usize {
+    pairs.iter().filter(|x| overlaps(x, false)).count()
 }
 
-fn to_range(raw_range: &str) -> HashSet<u32> {
-    let parts = raw_range.split('-').collect::<Vec<&str>>();
-    let start = parts[0].parse::<u32>().unwrap();
-    let end = parts[1].parse::<u32>().unwrap();
-    (start..end + 1).collect()
+pub fn parse(input: &str) -> Result<Vec<(IntervalSet<u32>, IntervalSet<u32>)>> {
+    error::parse_lines(input, |line| match line.split_once(',') {
+        Some((first, second)) => Ok((to_range(first)?, to_range(second)?)),
+        None => Err("expected two ranges".to_owned()),
+    })
 }
 
-fn overlaps(ranges: &[HashSet<u32>], fully: bool) -> bool {
-    assert!(ranges.len() == 2);
-    let first = ranges.get(0).unwrap();
-    let second = ranges.get(1).unwrap();
+fn to_range(raw_range: &str) -> std::result::Result<IntervalSet<u32>, String> {
+    let invalid = || format!("invalid range {:?}", raw_range);
+    let (start, end) = raw_range.split_once('-').ok_or_else(invalid)?;
+    let start = start.parse::<u32>().map_err(|_| invalid())?;
+    let end = end.parse::<u32>().map_err(|_| invalid())?;
+    let end = end.checked_add(1).ok_or_else(invalid)?;
+    Ok(IntervalSet::from(start..end))
+}
+
+fn overlaps((first, second): &(IntervalSet<u32>, IntervalSet<u32>), fully: bool) -> bool {
     match fully {
         true => first.is_subset(second) || second.is_subset(first),
         false => !first.is_disjoint(second),
@@ -50,7 +64,7 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        assert_eq!(part1(input), 2);
+        assert_eq!(part1(&parse(input).unwrap()), 2);
     }
 
     #[test]
@@ -61,6 +75,15 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        assert_eq!(part2(input), 4);
+        assert_eq!(part2(&parse(input).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("1-4294967294,2-3").is_ok());
+        assert_eq!(
+            parse("1-4294967295,2-3").unwrap_err().to_string(),
+            "line 1: invalid range \"1-4294967295\": `1-4294967295,2-3`"
+        );
     }
 }
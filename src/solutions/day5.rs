@@ -1,13 +1,32 @@
 use regex::Regex;
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Error, Result},
+    params::Params,
+};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (Vec<Vec<char>>, Vec<Instruction>);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(&input.0, &input.1).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(&input.0, &input.1).into()
+    }
 }
 
-fn part1(input: &str) -> String {
-    let mut stacks = parse_stacks(input);
-    let instructions = parse_instructions(input);
+/// Number of crates to move, source stack and target stack.
+pub type Instruction = (u32, usize, usize);
+
+pub fn part1(stacks: &[Vec<char>], instructions: &[Instruction]) -> String {
+    let mut stacks = stacks.to_vec();
     instructions.iter().for_each(|(num, source, target)| {
         (0..*num).for_each(|_| {
             let tmp = stacks[*source].pop().unwrap();
@@ -20,9 +39,8 @@ fn part1(input: &str) -> String {
         .collect::<String>()
 }
 
-fn part2(input: &str) -> String {
-    let mut stacks = parse_stacks(input);
-    let instructions = parse_instructions(input);
+pub fn part2(stacks: &[Vec<char>], instructions: &[Instruction]) -> String {
+    let mut stacks = stacks.to_vec();
     instructions.iter().for_each(|(num, source, target)| {
         let split_i = stacks[*source].len() - *num as usize;
         let mut to_move = stacks[*source].split_off(split_i);
@@ -34,9 +52,18 @@ fn part2(input: &str) -> String {
         .collect::<String>()
 }
 
-fn parse_stacks(input: &str) -> Vec<Vec<char>> {
-    let stacks_raw = input.split("\n\n").collect::<Vec<_>>()[0];
-    let max = stacks_raw.lines().map(|l| l.len()).max().unwrap();
+pub fn parse(input: &str) -> Result<(Vec<Vec<char>>, Vec<Instruction>)> {
+    let (stacks_raw, instructions_raw) = input.split_once("\n\n").ok_or_else(|| {
+        Error::input("expected stacks and instructions separated by a blank line")
+    })?;
+    let stacks = parse_stacks(stacks_raw);
+    let instructions = parse_instructions(instructions_raw, stacks.len())
+        .map_err(|e| e.offset(stacks_raw.lines().count() + 1))?;
+    Ok((stacks, instructions))
+}
+
+fn parse_stacks(stacks_raw: &str) -> Vec<Vec<char>> {
+    let max = stacks_raw.lines().map(|l| l.len()).max().unwrap_or(0);
     (1..max)
         .step_by(4)
         .map(|column_i| {
@@ -50,32 +77,21 @@ fn parse_stacks(input: &str) -> Vec<Vec<char>> {
         .collect()
 }
 
-fn parse_instructions(input: &str) -> Vec<(u32, usize, usize)> {
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-    input.split("\n\n").collect::<Vec<_>>()[1]
-        .lines()
-        .map(|line| {
-            let caps = re.captures(line).unwrap();
-            let num = caps
-                .get(1)
-                .map_or("", |m| m.as_str())
-                .parse::<u32>()
-                .unwrap();
-            let source = caps
-                .get(2)
-                .map_or("", |m| m.as_str())
-                .parse::<usize>()
-                .unwrap()
-                - 1;
-            let target = caps
-                .get(3)
-                .map_or("", |m| m.as_str())
-                .parse::<usize>()
-                .unwrap()
-                - 1;
-            (num, source, target)
-        })
-        .collect()
+fn parse_instructions(instructions_raw: &str, num_stacks: usize) -> Result<Vec<Instruction>> {
+    let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    error::parse_lines(instructions_raw, |line| {
+        let caps = re
+            .captures(line)
+            .ok_or("expected `move <n> from <stack> to <stack>`")?;
+        let num = caps[1]
+            .parse::<u32>()
+            .map_err(|_| "invalid number of crates")?;
+        let stack = |i: usize| match caps[i].parse::<usize>() {
+            Ok(n) if (1..=num_stacks).contains(&n) => Ok(n - 1),
+            _ => Err(format!("no stack {}", &caps[i])),
+        };
+        Ok((num, stack(2)?, stack(3)?))
+    })
 }
 
 #[cfg(test)]
@@ -93,7 +109,8 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
-        assert_eq!(part1(input), "CMZ".to_owned());
+        let (stacks, instructions) = parse(input).unwrap();
+        assert_eq!(part1(&stacks, &instructions), "CMZ".to_owned());
     }
 
     #[test]
@@ -107,6 +124,7 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
-        assert_eq!(part2(input), "MCD".to_owned());
+        let (stacks, instructions) = parse(input).unwrap();
+        assert_eq!(part2(&stacks, &instructions), "MCD".to_owned());
     }
 }
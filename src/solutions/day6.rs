@@ -1,22 +1,34 @@
 use std::collections::HashSet;
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+use super::{Answer, Solution};
+use crate::{error::Result, params::Params};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Vec<char>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.chars().collect())
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    solve(input, 4).unwrap()
+pub fn part1(signal: &[char]) -> usize {
+    solve(signal, 4).unwrap()
 }
 
-fn part2(input: &str) -> usize {
-    solve(input, 14).unwrap()
+pub fn part2(signal: &[char]) -> usize {
+    solve(signal, 14).unwrap()
 }
 
-fn solve(input: &str, window_size: usize) -> Option<usize> {
-    input
-        .chars()
-        .collect::<Vec<_>>()
+fn solve(signal: &[char], window_size: usize) -> Option<usize> {
+    signal
         .windows(window_size)
         .enumerate()
         .filter(|(_, w)| w.iter().collect::<HashSet<_>>().len() == window_size)
@@ -30,19 +42,49 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!(part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!(part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!(part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
-        assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
+        assert_eq!(
+            part1(&Day6::parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
+            5
+        );
+        assert_eq!(
+            part1(&Day6::parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
+            6
+        );
+        assert_eq!(
+            part1(&Day6::parse("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
+            10
+        );
+        assert_eq!(
+            part1(&Day6::parse("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
+            11
+        );
+        assert_eq!(
+            part1(&Day6::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap()),
+            7
+        );
     }
 
     #[test]
     fn part2_test() {
-        assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!(part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!(part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!(part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+        assert_eq!(
+            part2(&Day6::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap()),
+            19
+        );
+        assert_eq!(
+            part2(&Day6::parse("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap()),
+            23
+        );
+        assert_eq!(
+            part2(&Day6::parse("nppdvjthqldpwncqszvftbrmjlhg").unwrap()),
+            23
+        );
+        assert_eq!(
+            part2(&Day6::parse("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap()),
+            29
+        );
+        assert_eq!(
+            part2(&Day6::parse("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap()),
+            26
+        );
     }
 }
@@ -1,27 +1,42 @@
 use std::collections::BTreeMap;
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input).unwrap());
+use super::{Answer, Solution};
+use crate::{
+    error::{Error, Result},
+    params::Params,
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = BTreeMap<String, File>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).unwrap().into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let files = parse(input);
+pub fn part1(files: &BTreeMap<String, File>) -> usize {
     files
         .iter()
         .filter(|(_, file)| **file == File::Directory)
-        .map(|(path, _)| size_of(path, &files))
+        .map(|(path, _)| size_of(path, files))
         .filter(|size| *size <= 100000)
         .sum()
 }
 
-fn part2(input: &str) -> Option<usize> {
-    let files = parse(input);
-    let needed = 30000000 - (70000000 - size_of("/", &files));
+pub fn part2(files: &BTreeMap<String, File>) -> Option<usize> {
+    let needed = 30000000 - (70000000 - size_of("/", files));
     files
         .iter()
         .filter(|(_, file)| **file == File::Directory)
-        .map(|(path, _)| size_of(path, &files))
+        .map(|(path, _)| size_of(path, files))
         .filter(|size| *size >= needed)
         .min()
 }
@@ -37,31 +52,31 @@ fn size_of(dir: &str, files: &BTreeMap<String, File>) -> usize {
         .sum()
 }
 
-fn parse(input: &str) -> BTreeMap<String, File> {
+pub fn parse(input: &str) -> Result<BTreeMap<String, File>> {
     let mut current: Vec<&str> = Vec::new();
     let mut files: BTreeMap<String, File> = BTreeMap::new();
-    for line in input.lines() {
-        let parts = line.split(' ').collect::<Vec<&str>>();
-        match line {
-            "$ cd .." => {
+    for (i, line) in input.lines().enumerate() {
+        match line.split(' ').collect::<Vec<&str>>()[..] {
+            ["$", "cd", ".."] => {
                 current.pop();
             }
-            line if line.starts_with("$ cd ") => {
-                current.push(parts[2]);
+            ["$", "cd", dir] => {
+                current.push(dir);
+            }
+            ["$", "ls"] => {}
+            ["dir", name] => {
+                files.insert(absolute_path(&current, name), File::Directory);
             }
-            line if !line.starts_with('$') => {
-                files.insert(
-                    absolute_path(&current, parts[1]),
-                    match line.starts_with("dir") {
-                        true => File::Directory,
-                        false => File::Data(parts[0].parse().unwrap()),
-                    },
-                );
+            [size, name] => {
+                let size = size
+                    .parse()
+                    .map_err(|_| Error::parse(i + 1, line, "invalid file size"))?;
+                files.insert(absolute_path(&current, name), File::Data(size));
             }
-            _ => {}
+            _ => return Err(Error::parse(i + 1, line, "unexpected line")),
         }
     }
-    files
+    Ok(files)
 }
 
 fn absolute_path(current: &[&str], file: &str) -> String {
@@ -69,7 +84,7 @@ fn absolute_path(current: &[&str], file: &str) -> String {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-enum File {
+pub enum File {
     Directory,
     Data(usize),
 }
@@ -106,11 +121,11 @@ $ ls
 
     #[test]
     fn test_part1() {
-        assert_eq!(95437, part1(input()));
+        assert_eq!(95437, part1(&parse(input()).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(24933642, part2(input()).unwrap());
+        assert_eq!(24933642, part2(&parse(input()).unwrap()).unwrap());
     }
 }
@@ -1,83 +1,73 @@
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input).unwrap());
+use super::{Answer, Solution};
+use crate::{error::Result, params::Params, utils::grid::Grid};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Grid<u32>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).unwrap().into()
+    }
 }
 
-fn part1(input: &str) -> usize {
-    let trees = parse(input);
+pub fn part1(trees: &Grid<u32>) -> usize {
+    let views = in_every_direction(trees, |view| {
+        view.map_with_coordinate(|c, height| {
+            let row = view.row(c.y as usize);
+            row[..c.x as usize].iter().all(|h| h < height)
+        })
+    });
     trees
         .iter()
-        .enumerate()
-        .map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(x, _)| is_visible((y, *x), &trees))
-                .count()
-        })
-        .sum()
+        .filter(|(c, _)| views.iter().any(|view| *view.value_at(c).unwrap()))
+        .count()
 }
 
-fn part2(input: &str) -> Option<usize> {
-    let trees = parse(input);
+pub fn part2(trees: &Grid<u32>) -> Option<usize> {
+    let views = in_every_direction(trees, |view| {
+        view.map_with_coordinate(|c, height| {
+            let left = &view.row(c.y as usize)[..c.x as usize];
+            match left.iter().rev().position(|h| h >= height) {
+                Some(blocked_at) => blocked_at + 1,
+                None => left.len(),
+            }
+        })
+    });
     trees
         .iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(|(x, _)| scenic_score((y, x), &trees))
-                .collect::<Vec<usize>>()
+        .map(|(c, _)| {
+            views
+                .iter()
+                .map(|view| view.value_at(&c).unwrap())
+                .product()
         })
         .max()
 }
 
-fn is_visible(pos: (usize, usize), trees: &Vec<Vec<u32>>) -> bool {
-    let current_height = trees[pos.0][pos.1];
-    vec![
-        (0..pos.0).map(|y| trees[y][pos.1]).collect(),
-        (0..pos.1).map(|x| trees[pos.0][x]).collect(),
-        (pos.0 + 1..trees.len()).map(|y| trees[y][pos.1]).collect(),
-        (pos.1 + 1..trees[0].len())
-            .map(|x| trees[pos.0][x])
-            .collect(),
+/// Applies `look_left`, which computes something for every tree by looking
+/// to its left, to the forest seen from each side, turning each result back
+/// to line up with `trees`.
+fn in_every_direction<T: Clone>(
+    trees: &Grid<u32>,
+    look_left: impl Fn(&Grid<u32>) -> Grid<T>,
+) -> [Grid<T>; 4] {
+    [
+        look_left(trees),
+        look_left(&trees.flip_h()).flip_h(),
+        look_left(&trees.transpose()).transpose(),
+        look_left(&trees.transpose().flip_h()).flip_h().transpose(),
     ]
-    .into_iter()
-    .any(|dir: Vec<u32>| dir.into_iter().all(|h| h < current_height))
 }
 
-fn scenic_score(pos: (usize, usize), trees: &Vec<Vec<u32>>) -> usize {
-    let current_height = trees[pos.0][pos.1];
-    vec![
-        (0..pos.0).map(|y| trees[y][pos.1]).rev().collect(),
-        (0..pos.1).map(|x| trees[pos.0][x]).rev().collect(),
-        (pos.0 + 1..trees.len()).map(|y| trees[y][pos.1]).collect(),
-        (pos.1 + 1..trees[0].len())
-            .map(|x| trees[pos.0][x])
-            .collect(),
-    ]
-    .into_iter()
-    .map(|dir: Vec<u32>| {
-        let mut score = 0;
-        for height in dir {
-            score += 1;
-            if height >= current_height {
-                break;
-            }
-        }
-        score
-    })
-    .product()
-}
-
-fn parse(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+pub fn parse(input: &str) -> Result<Grid<u32>> {
+    Grid::parse(input)
 }
 
 #[cfg(test)]
@@ -91,7 +81,7 @@ mod tests {
 65332
 33549
 35390";
-        assert_eq!(21, part1(input));
+        assert_eq!(21, part1(&parse(input).unwrap()));
     }
 
     #[test]
@@ -101,6 +91,6 @@ mod tests {
 65332
 33549
 35390";
-        assert_eq!(Some(8), part2(input));
+        assert_eq!(Some(8), part2(&parse(input).unwrap()));
     }
 }
@@ -1,65 +1,72 @@
 use std::collections::HashSet;
 
-use crate::utils::coordinate::Coordinate;
+use super::{Answer, Solution};
+use crate::{
+    error::{self, Result},
+    params::Params,
+    utils::coordinate::{Coordinate, Direction},
+};
 
-pub fn run(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<(Direction, i32)>;
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input, _: &Params) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input, _: &Params) -> Answer {
+        part2(input).into()
+    }
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(motions: &[(Direction, i32)]) -> usize {
     let mut head = Coordinate::new(0, 0);
     let mut visited: Vec<_> = vec![Coordinate::new(0, 0)];
-    parse(input).into_iter().for_each(|(dir, n)| {
-        (0..n).for_each(|_| {
-            head = move_head(&head, dir);
+    motions.iter().for_each(|(dir, n)| {
+        (0..*n).for_each(|_| {
+            head += dir.unit();
             visited.push(follow(&head, visited.last().unwrap()));
         });
     });
     visited.into_iter().collect::<HashSet<Coordinate>>().len()
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(motions: &[(Direction, i32)]) -> usize {
     let mut knots = (0..10).map(|_| Coordinate::new(0, 0)).collect::<Vec<_>>();
     let mut visited: HashSet<Coordinate> = HashSet::new();
-    parse(input).into_iter().for_each(|(dir, n)| {
-        (0..n).for_each(|_| {
-            knots[0] = move_head(&knots[0], dir);
+    motions.iter().for_each(|(dir, n)| {
+        (0..*n).for_each(|_| {
+            knots[0] += dir.unit();
             (1..knots.len()).for_each(|i| {
                 knots[i] = follow(&knots[i - 1], &knots[i]);
             });
-            visited.insert(knots.last().unwrap().clone());
+            visited.insert(*knots.last().unwrap());
         });
     });
     visited.len()
 }
 
-fn move_head(head: &Coordinate, dir: &str) -> Coordinate {
-    match dir {
-        "R" => Coordinate::new(head.x + 1, head.y),
-        "D" => Coordinate::new(head.x, head.y - 1),
-        "L" => Coordinate::new(head.x - 1, head.y),
-        "U" => Coordinate::new(head.x, head.y + 1),
-        _ => panic!("Unexpected dir: {}", dir),
-    }
-}
-
 fn follow(head: &Coordinate, tail: &Coordinate) -> Coordinate {
-    if tail == head || tail.is_adjacent_to(head, true) {
-        return Coordinate::new(tail.x, tail.y);
+    match tail.chebyshev(head) <= 1 {
+        true => *tail,
+        false => *tail + (*head - *tail).signum(),
     }
-    Coordinate::new(
-        tail.x + (head.x - tail.x).signum(),
-        tail.y + (head.y - tail.y).signum(),
-    )
 }
 
-fn parse(input: &str) -> Vec<(&str, i32)> {
-    input
-        .lines()
-        .map(|line| line.split(' ').collect::<Vec<&str>>())
-        .map(|parts| (parts[0], parts[1].parse::<i32>().unwrap()))
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<(Direction, i32)>> {
+    error::parse_lines(input, |line| {
+        match line.split(' ').collect::<Vec<&str>>()[..] {
+            [dir @ ("R" | "D" | "L" | "U"), n] => match n.parse::<i32>() {
+                Ok(n) => Ok((dir.parse()?, n)),
+                Err(_) => Err(format!("invalid number of steps {:?}", n)),
+            },
+            _ => Err("expected `<R|D|L|U> <steps>`".to_owned()),
+        }
+    })
 }
 
 #[cfg(test)]
@@ -76,7 +83,7 @@ R 4
 D 1
 L 5
 R 2";
-        assert_eq!(13, part1(input));
+        assert_eq!(13, part1(&parse(input).unwrap()));
     }
 
     #[test]
@@ -89,7 +96,7 @@ R 4
 D 1
 L 5
 R 2";
-        assert_eq!(1, part2(input));
+        assert_eq!(1, part2(&parse(input).unwrap()));
     }
 
     #[test]
@@ -102,6 +109,6 @@ R 17
 D 10
 L 25
 U 20";
-        assert_eq!(36, part2(input));
+        assert_eq!(36, part2(&parse(input).unwrap()));
     }
 }
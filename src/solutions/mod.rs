@@ -1,3 +1,11 @@
+use std::{fmt, time::Duration};
+
+use crate::{
+    error,
+    params::{Param, Params},
+    runner,
+};
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -14,3 +22,195 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+/// All registered solutions, in day order.
+pub const SOLUTIONS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn Solver> {
+    SOLUTIONS.iter().find(|solver| solver.day() == day).copied()
+}
+
+/// A puzzle solution: the input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+    /// Parameters the parts read from [`Params`], settable with `--param`.
+    const PARAMS: &'static [Param] = &[];
+    type Input;
+    fn parse(input: &str) -> error::Result<Self::Input>;
+    fn part1(input: &Self::Input, params: &Params) -> Answer;
+    fn part2(input: &Self::Input, params: &Params) -> Answer;
+}
+
+/// Object safe view of a [`Solution`], used to call any day generically.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn params(&self) -> &'static [Param];
+    fn solve(&self, input: &str, config: &Config) -> Run;
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+    fn solve(&self, input: &str, config: &Config) -> Run {
+        let params = match Params::new(S::PARAMS, &config.params) {
+            Ok(params) => params,
+            Err(e) => return Run::failed(config, Duration::ZERO, e),
+        };
+        let (input, parse) = runner::timed(|| S::parse(input));
+        let input = match input {
+            Ok(Ok(input)) => input,
+            Ok(Err(e)) => return Run::failed(config, parse, e.with_day(S::DAY).to_string()),
+            Err(e) => return Run::failed(config, parse, format!("parse failed: {}", e)),
+        };
+        let parts = config
+            .parts()
+            .into_iter()
+            .map(|part| {
+                let f = match part {
+                    1 => S::part1,
+                    _ => S::part2,
+                };
+                let (answer, elapsed) = runner::timed(|| f(&input, &params));
+                PartResult {
+                    part,
+                    answer,
+                    elapsed,
+                }
+            })
+            .collect();
+        Run {
+            parse,
+            parts,
+            error: None,
+        }
+    }
+}
+
+/// Which parts to run, and overrides for the day's declared params.
+#[derive(Debug, Default)]
+pub struct Config {
+    pub part: Option<u8>,
+    pub params: Vec<(String, String)>,
+}
+
+impl Config {
+    pub fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+/// Timings and answers from solving one day.
+#[derive(Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub parts: Vec<PartResult>,
+    /// Why no part could be attempted, e.g. malformed input.
+    pub error: Option<String>,
+}
+
+impl Run {
+    /// A run where none of the configured parts could be attempted.
+    pub fn failed(config: &Config, parse: Duration, error: String) -> Run {
+        Run {
+            parse,
+            parts: config
+                .parts()
+                .into_iter()
+                .map(|part| PartResult {
+                    part,
+                    answer: Err(error.clone()),
+                    elapsed: Duration::ZERO,
+                })
+                .collect(),
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Answer {
+    /// Wide enough to hold any of the integer types answers come as.
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from_number() {
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(usize::MAX), Answer::Number(usize::MAX as i128));
+    }
+}
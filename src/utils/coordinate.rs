@@ -1,23 +1,356 @@
-#[derive(PartialEq, Eq, Debug, Hash, Clone)]
-pub struct Coordinate {
-    pub x: i32,
-    pub y: i32,
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Sub},
+    str::FromStr,
+};
+
+/// The signed integer types a coordinate can be made of.
+pub trait Number:
+    Copy + Ord + Hash + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// The unsigned type of the same width, which distances are measured in.
+    type Unsigned: Copy + Ord + Add<Output = Self::Unsigned>;
+    const ZERO: Self;
+    const ONE: Self;
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+    fn signum(self) -> Self;
+}
+
+macro_rules! number {
+    ($($t:ty => $u:ty),*) => {
+        $(impl Number for $t {
+            type Unsigned = $u;
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+            fn abs_diff(self, other: $t) -> $u {
+                <$t>::abs_diff(self, other)
+            }
+            fn signum(self) -> $t {
+                <$t>::signum(self)
+            }
+        })*
+    };
+}
+
+number!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone, Copy)]
+pub struct Coordinate<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+pub type Coordinate32 = Coordinate<i32>;
+pub type Coordinate64 = Coordinate<i64>;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone, Copy)]
+pub struct Coordinate3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The smallest rectangle holding a set of coordinates, corners included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+/// Which cells around a coordinate count as its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood<'a> {
+    /// The four orthogonal neighbours.
+    VonNeumann,
+    /// All eight surrounding cells, diagonals included.
+    Moore,
+    /// Neighbours at the given `(dx, dy)` offsets, visited in that order.
+    Custom(&'a [(i32, i32)]),
+}
+
+impl<'a> Neighbourhood<'a> {
+    pub fn offsets(&self) -> &'a [(i32, i32)] {
+        match self {
+            Neighbourhood::VonNeumann => &[(0, 1), (1, 0), (0, -1), (-1, 0)],
+            Neighbourhood::Moore => &[
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+            ],
+            Neighbourhood::Custom(offsets) => offsets,
+        }
+    }
 }
 
-impl Coordinate {
-    pub fn new(x: i32, y: i32) -> Coordinate {
+impl<T: Number> Coordinate<T> {
+    pub fn new(x: T, y: T) -> Coordinate<T> {
         Coordinate { x, y }
     }
+    pub fn manhattan(&self, other: &Coordinate<T>) -> T::Unsigned {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+    pub fn chebyshev(&self, other: &Coordinate<T>) -> T::Unsigned {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+    /// Each component replaced by its sign, giving a single step towards the
+    /// direction `self` points in.
+    pub fn signum(&self) -> Coordinate<T> {
+        Coordinate::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Coordinate<i32> {
+    pub fn neighbours<'a>(
+        &'a self,
+        neighbourhood: Neighbourhood<'a>,
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(|(dx, dy)| Coordinate::new(self.x + dx, self.y + dy))
+    }
     pub fn is_adjacent_to(&self, other: &Coordinate, diagonally: bool) -> bool {
-        if self.x == other.x && self.y.abs_diff(other.y) == 1 {
-            return true;
+        let neighbourhood = match diagonally {
+            true => Neighbourhood::Moore,
+            false => Neighbourhood::VonNeumann,
+        };
+        self.neighbours(neighbourhood).any(|c| c == *other)
+    }
+}
+
+impl Bounds {
+    /// Bounds of `points`, or `None` if there are none.
+    pub fn of<'a>(points: impl IntoIterator<Item = &'a Coordinate>) -> Option<Bounds> {
+        points.into_iter().fold(None, |bounds, c| {
+            Some(match bounds {
+                None => Bounds { min: *c, max: *c },
+                Some(Bounds { min, max }) => Bounds {
+                    min: Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+                    max: Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+                },
+            })
+        })
+    }
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+}
+
+impl<T: Number> Coordinate3<T> {
+    pub fn new(x: T, y: T, z: T) -> Coordinate3<T> {
+        Coordinate3 { x, y, z }
+    }
+    pub fn manhattan(&self, other: &Coordinate3<T>) -> T::Unsigned {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+    /// The six coordinates sharing a face with this one.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Coordinate3<T>> + '_ {
+        self.neighbours26()
+            .filter(move |c| c.manhattan(self) == T::ONE.abs_diff(T::ZERO))
+    }
+    /// The 26 coordinates sharing a face, edge or corner with this one.
+    pub fn neighbours26(&self) -> impl Iterator<Item = Coordinate3<T>> + '_ {
+        let steps = [T::ZERO - T::ONE, T::ZERO, T::ONE];
+        steps
+            .into_iter()
+            .flat_map(move |dx| steps.into_iter().map(move |dy| (dx, dy)))
+            .flat_map(move |(dx, dy)| steps.into_iter().map(move |dz| (dx, dy, dz)))
+            .filter(|d| *d != (T::ZERO, T::ZERO, T::ZERO))
+            .map(|(dx, dy, dz)| Coordinate3::new(self.x + dx, self.y + dy, self.z + dz))
+    }
+    /// The smallest and largest corner of the box holding all of `points`,
+    /// or `None` if there are none.
+    pub fn bounding_box<'a>(
+        points: impl IntoIterator<Item = &'a Coordinate3<T>>,
+    ) -> Option<(Coordinate3<T>, Coordinate3<T>)>
+    where
+        T: 'a,
+    {
+        points.into_iter().fold(None, |bounds, c| {
+            Some(match bounds {
+                None => (*c, *c),
+                Some((min, max)) => (
+                    Coordinate3::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)),
+                    Coordinate3::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)),
+                ),
+            })
+        })
+    }
+    /// Whether this lies within the box from `min` to `max`, both included.
+    pub fn is_within(&self, min: &Coordinate3<T>, max: &Coordinate3<T>) -> bool {
+        (min.x..=max.x).contains(&self.x)
+            && (min.y..=max.y).contains(&self.y)
+            && (min.z..=max.z).contains(&self.z)
+    }
+}
+
+macro_rules! ops {
+    ($name:ident { $($field:ident),* }) => {
+        impl<T: Number> Add for $name<T> {
+            type Output = $name<T>;
+            fn add(self, other: $name<T>) -> $name<T> {
+                $name { $($field: self.$field + other.$field),* }
+            }
         }
-        if self.y == other.y && self.x.abs_diff(other.x) == 1 {
-            return true;
+
+        impl<T: Number> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: $name<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Number> Sub for $name<T> {
+            type Output = $name<T>;
+            fn sub(self, other: $name<T>) -> $name<T> {
+                $name { $($field: self.$field - other.$field),* }
+            }
         }
-        if diagonally && self.x.abs_diff(other.x) == 1 && self.y.abs_diff(other.y) == 1 {
-            return true;
+
+        impl<T: Number> Mul<T> for $name<T> {
+            type Output = $name<T>;
+            fn mul(self, n: T) -> $name<T> {
+                $name { $($field: self.$field * n),* }
+            }
+        }
+    };
+}
+
+ops!(Coordinate { x, y });
+ops!(Coordinate3 { x, y, z });
+
+/// A compass direction on a grid where `y` grows downwards, as it does in
+/// `Grid` and puzzle inputs.
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+    pub fn unit<T: Number>(&self) -> Coordinate<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Direction::Up => Coordinate::new(zero, zero - one),
+            Direction::Right => Coordinate::new(one, zero),
+            Direction::Down => Coordinate::new(zero, one),
+            Direction::Left => Coordinate::new(zero - one, zero),
+        }
+    }
+    pub fn turn_right(&self) -> Direction {
+        Direction::ALL[(*self as usize + 1) % 4]
+    }
+    pub fn turn_left(&self) -> Direction {
+        Direction::ALL[(*self as usize + 3) % 4]
+    }
+    pub fn opposite(&self) -> Direction {
+        Direction::ALL[(*self as usize + 2) % 4]
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Direction, String> {
+        match s {
+            "U" | "N" => Ok(Direction::Up),
+            "R" | "E" => Ok(Direction::Right),
+            "D" | "S" => Ok(Direction::Down),
+            "L" | "W" => Ok(Direction::Left),
+            _ => Err(format!("unknown direction {:?}", s)),
         }
-        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Coordinate::new(0, 0);
+        assert_eq!(origin.neighbours(Neighbourhood::VonNeumann).count(), 4);
+        assert_eq!(origin.neighbours(Neighbourhood::Moore).count(), 8);
+        assert_eq!(
+            origin
+                .neighbours(Neighbourhood::Custom(&[(2, 0), (0, -3)]))
+                .collect::<Vec<_>>(),
+            vec![Coordinate::new(2, 0), Coordinate::new(0, -3)]
+        );
+        assert!(origin.is_adjacent_to(&Coordinate::new(1, 1), true));
+        assert!(!origin.is_adjacent_to(&Coordinate::new(1, 1), false));
+        assert!(!origin.is_adjacent_to(&origin, true));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Coordinate::new(1, -2);
+        let b = Coordinate::new(4, 2);
+        assert_eq!(a + b, Coordinate::new(5, 0));
+        assert_eq!(b - a, Coordinate::new(3, 4));
+        assert_eq!(a * 3, Coordinate::new(3, -6));
+        assert_eq!((b - a).signum(), Coordinate::new(1, 1));
+        assert_eq!(a.manhattan(&b), 7u32);
+        assert_eq!(a.chebyshev(&b), 4u32);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!("N".parse::<Direction>(), Ok(Direction::Up));
+        assert_eq!("L".parse::<Direction>(), Ok(Direction::Left));
+        assert!("X".parse::<Direction>().is_err());
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Direction::Down.unit(), Coordinate::new(0, 1));
+        assert_eq!(Direction::Left.unit(), Coordinate64::new(-1, 0));
+    }
+
+    #[test]
+    fn test_wide_coordinates() {
+        let a = Coordinate64::new(4_000_000, 3_000_000);
+        assert_eq!(
+            a * 4_000_000,
+            Coordinate64::new(16_000_000_000_000, 12_000_000_000_000)
+        );
+        assert_eq!(a.manhattan(&Coordinate64::new(0, 0)), 7_000_000u64);
+    }
+
+    #[test]
+    fn test_coordinate3() {
+        let origin = Coordinate3::new(0, 0, 0);
+        assert_eq!(origin.neighbours6().count(), 6);
+        assert_eq!(origin.neighbours26().count(), 26);
+        assert!(origin.neighbours6().all(|c| c.manhattan(&origin) == 1u32));
+        let points = [
+            Coordinate3::new(1, -2, 3),
+            Coordinate3::new(-1, 5, 0),
+            Coordinate3::new(0, 0, 7),
+        ];
+        let (min, max) = Coordinate3::bounding_box(&points).unwrap();
+        assert_eq!(min, Coordinate3::new(-1, -2, 0));
+        assert_eq!(max, Coordinate3::new(1, 5, 7));
+        assert!(origin.is_within(&min, &max));
+        assert!(!Coordinate3::new(2, 0, 0).is_within(&min, &max));
+        assert_eq!(
+            Coordinate3::new(1, 2, 3) + Coordinate3::new(1, 1, 1) * 2,
+            Coordinate3::new(3, 4, 5)
+        );
     }
 }
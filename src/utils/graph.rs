@@ -0,0 +1,252 @@
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub target_id: usize,
+    pub weight: usize,
+}
+
+/// A directed, weighted graph whose nodes are identified by string labels
+/// and stored at indices in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<N> {
+    ids: HashMap<String, usize>,
+    labels: Vec<String>,
+    nodes: Vec<N>,
+    edges: Vec<Vec<Edge>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Graph<N> {
+        Graph {
+            ids: HashMap::new(),
+            labels: Vec::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+    /// Adds a node, or replaces the value of the node with the same label.
+    /// Returns the node's index.
+    pub fn add_node(&mut self, label: &str, value: N) -> usize {
+        if let Some(&id) = self.ids.get(label) {
+            self.nodes[id] = value;
+            return id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(label.to_owned(), id);
+        self.labels.push(label.to_owned());
+        self.nodes.push(value);
+        self.edges.push(Vec::new());
+        id
+    }
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: usize) {
+        self.edges[from].push(Edge {
+            target_id: to,
+            weight,
+        });
+    }
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+    pub fn id(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+    pub fn label(&self, id: usize) -> &str {
+        &self.labels[id]
+    }
+    pub fn node(&self, id: usize) -> &N {
+        &self.nodes[id]
+    }
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+    pub fn edges(&self, id: usize) -> &[Edge] {
+        &self.edges[id]
+    }
+    /// Distances between every pair of nodes using Floyd–Warshall, `None`
+    /// where there is no path.
+    pub fn shortest_paths(&self) -> Vec<Vec<Option<usize>>> {
+        let n = self.len();
+        let mut distances = vec![vec![None; n]; n];
+        for (from, edges) in self.edges.iter().enumerate() {
+            distances[from][from] = Some(0);
+            for edge in edges {
+                let d = &mut distances[from][edge.target_id];
+                *d = Some(d.map_or(edge.weight, |d: usize| d.min(edge.weight)));
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if let (Some(ik), Some(kj)) = (distances[i][k], distances[k][j]) {
+                        if distances[i][j].is_none_or(|ij| ik + kj < ij) {
+                            distances[i][j] = Some(ik + kj);
+                        }
+                    }
+                }
+            }
+        }
+        distances
+    }
+    /// A graph of only the nodes that `keep` selects, with an edge between
+    /// each pair of them that are connected, weighted by the length of the
+    /// shortest path through the original graph.
+    pub fn contract(&self, mut keep: impl FnMut(usize, &N) -> bool) -> Graph<N>
+    where
+        N: Clone,
+    {
+        let distances = self.shortest_paths();
+        let kept = (0..self.len())
+            .filter(|&id| keep(id, &self.nodes[id]))
+            .collect::<Vec<_>>();
+        let mut contracted = Graph::new();
+        for &id in &kept {
+            contracted.add_node(&self.labels[id], self.nodes[id].clone());
+        }
+        for (from, &i) in kept.iter().enumerate() {
+            for (to, &j) in kept.iter().enumerate() {
+                if let (true, Some(weight)) = (i != j, distances[i][j]) {
+                    contracted.add_edge(from, to, weight);
+                }
+            }
+        }
+        contracted
+    }
+    /// Node ids ordered so that every edge points forward, taking the lowest
+    /// id whenever there is a choice. `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.len()];
+        self.edges
+            .iter()
+            .flatten()
+            .for_each(|edge| incoming[edge.target_id] += 1);
+        let mut ready = (0..self.len())
+            .filter(|&id| incoming[id] == 0)
+            .collect::<BTreeSet<_>>();
+        let mut order = Vec::new();
+        while let Some(id) = ready.pop_first() {
+            order.push(id);
+            for edge in &self.edges[id] {
+                incoming[edge.target_id] -= 1;
+                if incoming[edge.target_id] == 0 {
+                    ready.insert(edge.target_id);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+    /// The nodes of some cycle in the order the edges visit them, or `None`
+    /// if the graph is acyclic.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+        let mut states = vec![State::New; self.len()];
+        for root in 0..self.len() {
+            if states[root] != State::New {
+                continue;
+            }
+            // Each entry is a node on the current path and the next of its
+            // edges to follow.
+            let mut path = vec![(root, 0)];
+            states[root] = State::OnPath;
+            while let Some((id, next)) = path.last_mut() {
+                let id = *id;
+                match self.edges[id].get(*next) {
+                    Some(edge) => {
+                        *next += 1;
+                        match states[edge.target_id] {
+                            State::New => {
+                                states[edge.target_id] = State::OnPath;
+                                path.push((edge.target_id, 0));
+                            }
+                            State::OnPath => {
+                                let start =
+                                    path.iter().position(|(n, _)| *n == edge.target_id).unwrap();
+                                return Some(path[start..].iter().map(|(n, _)| *n).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        states[id] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -> b -> c -> d with a 5-weight shortcut from a to d, plus an
+    // unconnected e.
+    fn graph() -> Graph<u32> {
+        let mut graph = Graph::new();
+        for (label, value) in [("a", 0), ("b", 1), ("c", 0), ("d", 2), ("e", 3)] {
+            graph.add_node(label, value);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(0, 3, 5);
+        graph
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let distances = graph().shortest_paths();
+        assert_eq!(distances[0][3], Some(3));
+        assert_eq!(distances[3][0], None);
+        assert_eq!(distances[4][4], Some(0));
+    }
+
+    #[test]
+    fn test_contract() {
+        let graph = graph();
+        let contracted = graph.contract(|id, value| id == 0 || *value > 0);
+        assert_eq!(contracted.len(), 4);
+        assert_eq!(contracted.id("d"), Some(2));
+        assert_eq!(contracted.label(1), "b");
+        assert_eq!(
+            contracted.edges(0),
+            &[
+                Edge {
+                    target_id: 1,
+                    weight: 1
+                },
+                Edge {
+                    target_id: 2,
+                    weight: 3
+                }
+            ]
+        );
+        assert!(contracted.edges(3).is_empty());
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let mut graph = graph();
+        assert_eq!(graph.topological_sort(), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(graph.find_cycle(), None);
+        graph.add_edge(3, 1, 1);
+        assert_eq!(graph.topological_sort(), None);
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 3]));
+    }
+}
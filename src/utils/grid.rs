@@ -1,66 +1,401 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
 
-use super::coordinate::Coordinate;
+use super::coordinate::{Bounds, Coordinate, Neighbourhood};
+use crate::error::{Error, Result};
 
-pub struct Grid<S>
+/// A rectangular grid of cells stored row by row, with `(0, 0)` in the top
+/// left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<S> {
+    width: usize,
+    height: usize,
+    data: Vec<S>,
+}
+
+impl<S> Grid<S> {
+    /// A `width` by `height` grid with `f(coordinate)` as each cell's value.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coordinate) -> S) -> Grid<S> {
+        let data = (0..width * height)
+            .map(|i| f(Coordinate::new((i % width) as i32, (i / width) as i32)))
+            .collect();
+        Grid {
+            width,
+            height,
+            data,
+        }
+    }
+    /// Builds a grid from text, one row per line, with `f` mapping each char
+    /// to a value or `None` if the char is not a valid cell.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> Option<S>) -> Result<Grid<S>> {
+        let mut data: Vec<S> = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let expected = *width.get_or_insert(line.chars().count());
+            for (x, c) in line.chars().enumerate() {
+                data.push(f(c).ok_or_else(|| {
+                    Error::parse(
+                        y + 1,
+                        line,
+                        format!("invalid cell {:?} at column {}", c, x + 1),
+                    )
+                })?);
+            }
+            if data.len() != (y + 1) * expected {
+                return Err(Error::parse(
+                    y + 1,
+                    line,
+                    format!("expected {} cells like the first row", expected),
+                ));
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or_default(),
+            height,
+            data,
+        })
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// The rectangle from `(0, 0)` to the bottom right cell.
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Coordinate::new(0, 0),
+            max: Coordinate::new(self.width as i32 - 1, self.height as i32 - 1),
+        }
+    }
+    pub fn contains(&self, c: &Coordinate) -> bool {
+        self.index_of(c).is_some()
+    }
+    pub fn value_at(&self, c: &Coordinate) -> Option<&S> {
+        self.index_of(c).map(|i| &self.data[i])
+    }
+    pub fn value_at_mut(&mut self, c: &Coordinate) -> Option<&mut S> {
+        self.index_of(c).map(|i| &mut self.data[i])
+    }
+    /// Replaces the value at `c`. Panics if `c` is outside the grid.
+    pub fn set(&mut self, c: &Coordinate, value: S) {
+        self[*c] = value;
+    }
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[S] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+    /// Cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &S> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.data.iter().skip(x).step_by(self.width)
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[S]> {
+        self.data.chunks(self.width.max(1))
+    }
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &S>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+    /// Every cell with its coordinate, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &S)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (self.coordinate_of(i), value))
+    }
+    /// The orthogonal neighbours of `source` inside the grid, with their values.
+    pub fn adjacent(&self, source: &Coordinate) -> Vec<(Coordinate, &S)> {
+        self.neighbours(source, Neighbourhood::VonNeumann)
+    }
+    /// The neighbours of `source` inside the grid, with their values.
+    pub fn neighbours(
+        &self,
+        source: &Coordinate,
+        neighbourhood: Neighbourhood,
+    ) -> Vec<(Coordinate, &S)> {
+        source
+            .neighbours(neighbourhood)
+            .filter_map(|c| self.index_of(&c).map(|i| (c, &self.data[i])))
+            .collect()
+    }
+    /// A grid of the same size with every value replaced by `f(value)`.
+    pub fn map<T>(&self, f: impl FnMut(&S) -> T) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+    /// Like `map`, with the coordinate of each value passed along.
+    pub fn map_with_coordinate<T>(&self, mut f: impl FnMut(Coordinate, &S) -> T) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.iter().map(|(c, value)| f(c, value)).collect(),
+        }
+    }
+    fn index_of(&self, c: &Coordinate) -> Option<usize> {
+        match (usize::try_from(c.x), usize::try_from(c.y)) {
+            (Ok(x), Ok(y)) if x < self.width && y < self.height => Some(y * self.width + x),
+            _ => None,
+        }
+    }
+    fn coordinate_of(&self, i: usize) -> Coordinate {
+        Coordinate::new((i % self.width) as i32, (i / self.width) as i32)
+    }
+}
+
+impl<S> Grid<S>
 where
-    S: FromStr + PartialEq,
+    S: Clone,
 {
-    data: HashMap<Coordinate, S>,
+    /// A `width` by `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: S) -> Grid<S> {
+        Grid {
+            width,
+            height,
+            data: vec![fill; width * height],
+        }
+    }
+    /// A new `width` by `height` grid, with `f(x, y)` giving the index in
+    /// `self` that each of its cells copies.
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> usize,
+    ) -> Grid<S> {
+        Grid {
+            width,
+            height,
+            data: (0..width * height)
+                .map(|i| self.data[f(i % width, i / width)].clone())
+                .collect(),
+        }
+    }
+    /// Rotated a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<S> {
+        let (w, h) = (self.width, self.height);
+        self.rearranged(h, w, |x, y| (h - 1 - x) * w + y)
+    }
+    /// Rotated a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Grid<S> {
+        let (w, h) = (self.width, self.height);
+        self.rearranged(h, w, |x, y| x * w + (w - 1 - y))
+    }
+    /// Mirrored left to right.
+    pub fn flip_h(&self) -> Grid<S> {
+        let w = self.width;
+        self.rearranged(w, self.height, |x, y| y * w + (w - 1 - x))
+    }
+    /// Mirrored top to bottom.
+    pub fn flip_v(&self) -> Grid<S> {
+        let (w, h) = (self.width, self.height);
+        self.rearranged(w, h, |x, y| (h - 1 - y) * w + x)
+    }
+    /// Mirrored along the diagonal from the top left corner, so rows become
+    /// columns.
+    pub fn transpose(&self) -> Grid<S> {
+        let w = self.width;
+        self.rearranged(self.height, w, |x, y| x * w + y)
+    }
+    /// The cells within `bounds`. Panics if `bounds` reaches outside the grid.
+    pub fn subgrid(&self, bounds: &Bounds) -> Grid<S> {
+        assert!(
+            self.contains(&bounds.min) && self.contains(&bounds.max),
+            "{:?} reaches outside the grid",
+            bounds
+        );
+        let (x0, y0) = (bounds.min.x as usize, bounds.min.y as usize);
+        let w = self.width;
+        self.rearranged(bounds.width(), bounds.height(), |x, y| {
+            (y0 + y) * w + x0 + x
+        })
+    }
+    /// The grid repeated `nx` times across and `ny` times down.
+    pub fn tile(&self, nx: usize, ny: usize) -> Grid<S> {
+        let (w, h) = (self.width, self.height);
+        self.rearranged(w * nx, h * ny, |x, y| (y % h) * w + x % w)
+    }
 }
 
 impl<S> Grid<S>
 where
-    S: FromStr + PartialEq,
+    S: PartialEq,
 {
-    pub fn find(&self, v: S) -> Vec<&Coordinate> {
-        self.data
-            .iter()
+    pub fn find(&self, v: S) -> Vec<Coordinate> {
+        self.iter()
             .filter(|(_, value)| **value == v)
             .map(|(coordinate, _)| coordinate)
             .collect()
     }
-    pub fn find_first(&self, v: S) -> Option<&Coordinate> {
-        self.find(v).into_iter().next()
-    }
-    pub fn value_at(&self, c: &Coordinate) -> Option<&S> {
-        self.data.get(c)
-    }
-    pub fn adjacent(&self, source: &Coordinate) -> Vec<&Coordinate> {
-        vec![
-            self.data
-                .get_key_value(&Coordinate::new(source.x, source.y + 1)),
-            self.data
-                .get_key_value(&Coordinate::new(source.x + 1, source.y)),
-            self.data
-                .get_key_value(&Coordinate::new(source.x, source.y - 1)),
-            self.data
-                .get_key_value(&Coordinate::new(source.x - 1, source.y)),
-        ]
-        .into_iter()
-        .flatten()
-        .map(|(coordinate, _)| coordinate)
-        .collect()
+    pub fn find_first(&self, v: S) -> Option<Coordinate> {
+        self.data
+            .iter()
+            .position(|value| *value == v)
+            .map(|i| self.coordinate_of(i))
     }
 }
 
 impl<S> Grid<S>
 where
-    S: FromStr + PartialEq,
+    S: FromStr,
 {
-    pub fn parse(input: &str) -> Grid<S> {
-        let mut data: HashMap<Coordinate, S> = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                data.insert(
-                    Coordinate::new(x as i32, y as i32),
-                    match c.to_string().parse::<S>() {
-                        Ok(v) => v,
-                        Err(_) => panic!("Failed to parse grid"),
-                    },
-                );
-            }
+    /// Builds a grid from text, one row per line, parsing each char on its
+    /// own as a value.
+    pub fn parse(input: &str) -> Result<Grid<S>> {
+        Grid::parse_with(input, |c| c.to_string().parse().ok())
+    }
+}
+
+impl<S> Index<Coordinate> for Grid<S> {
+    type Output = S;
+    fn index(&self, c: Coordinate) -> &S {
+        self.value_at(&c)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", c))
+    }
+}
+
+impl<S> IndexMut<Coordinate> for Grid<S> {
+    fn index_mut(&mut self, c: Coordinate) -> &mut S {
+        self.value_at_mut(&c)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", c))
+    }
+}
+
+/// Collects cells into a grid just big enough to hold them all, from
+/// `(0, 0)` to the largest `x` and `y`. Cells not given are left at their
+/// default value. Panics on negative coordinates.
+impl<S> FromIterator<(Coordinate, S)> for Grid<S>
+where
+    S: Clone + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (Coordinate, S)>>(iter: I) -> Grid<S> {
+        let cells = iter.into_iter().collect::<Vec<_>>();
+        assert!(
+            cells.iter().all(|(c, _)| c.x >= 0 && c.y >= 0),
+            "negative coordinates cannot be collected into a grid"
+        );
+        let width = cells.iter().map(|(c, _)| c.x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|(c, _)| c.y + 1).max().unwrap_or(0);
+        let mut grid = Grid::new(width as usize, height as usize, S::default());
+        for (c, value) in cells {
+            grid[c] = value;
         }
-        Grid { data }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse("123\n456").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.value_at(&Coordinate::new(2, 1)), Some(&6));
+        assert_eq!(grid.value_at(&Coordinate::new(3, 0)), None);
+        assert_eq!(grid.value_at(&Coordinate::new(-1, 0)), None);
+        assert!(Grid::<u8>::parse("123\n45").is_err());
+        assert!(Grid::<u8>::parse("12x").is_err());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid();
+        assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.flip_h().to_string(), "321\n654");
+        assert_eq!(grid.flip_v().to_string(), "456\n123");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        let bounds = Bounds {
+            min: Coordinate::new(1, 0),
+            max: Coordinate::new(2, 1),
+        };
+        assert_eq!(grid.subgrid(&bounds).to_string(), "23\n56");
+        assert_eq!(
+            grid.tile(2, 2).to_string(),
+            "123123\n456456\n123123\n456456"
+        );
+        assert_eq!(
+            grid.map(|v| v % 2 == 0).to_string(),
+            "falsetruefalse\ntruefalsetrue"
+        );
+        assert_eq!(
+            grid.map_with_coordinate(|c, v| c.x as u8 + v).to_string(),
+            "135\n468"
+        );
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut grid = Grid::new(3, 2, '.');
+        grid.set(&Coordinate::new(1, 0), '#');
+        grid[Coordinate::new(2, 1)] = '@';
+        assert_eq!(grid[Coordinate::new(1, 0)], '#');
+        assert_eq!(grid.to_string(), ".#.\n..@");
+        let grid = Grid::from_fn(2, 2, |c| c.x + c.y);
+        assert_eq!(grid.to_string(), "01\n12");
+        let grid = [(Coordinate::new(2, 1), 5u8), (Coordinate::new(0, 0), 1)]
+            .into_iter()
+            .collect::<Grid<_>>();
+        assert_eq!(grid.to_string(), "100\n005");
+        let grid = Grid::parse_with("#.\n.#", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert!(grid[Coordinate::new(1, 1)]);
+        assert!(Grid::parse_with("#x", |c| (c == '#').then_some(true)).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside() {
+        let _ = grid()[Coordinate::new(3, 0)];
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::<char>::parse("ab\nba").unwrap();
+        assert_eq!(
+            grid.find('a'),
+            vec![Coordinate::new(0, 0), Coordinate::new(1, 1)]
+        );
+        assert_eq!(grid.find_first('b'), Some(Coordinate::new(1, 0)));
+        assert_eq!(
+            grid.adjacent(&Coordinate::new(0, 0)),
+            vec![(Coordinate::new(0, 1), &'b'), (Coordinate::new(1, 0), &'b')]
+        );
+        assert_eq!(
+            grid.neighbours(&Coordinate::new(0, 0), Neighbourhood::Moore),
+            vec![
+                (Coordinate::new(0, 1), &'b'),
+                (Coordinate::new(1, 1), &'a'),
+                (Coordinate::new(1, 0), &'b')
+            ]
+        );
     }
 }
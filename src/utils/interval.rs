@@ -0,0 +1,153 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as sorted, disjoint half-open ranges. Ranges that
+/// overlap or touch are coalesced as they are inserted.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: Vec::new() }
+    }
+    /// The coalesced ranges, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// Total number of values covered.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |total, r| total + (r.end - r.start))
+    }
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let from = self.ranges.partition_point(|r| r.end < range.start);
+        let to = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = match from < to {
+            true => {
+                self.ranges[from].start.min(range.start)..self.ranges[to - 1].end.max(range.end)
+            }
+            false => range,
+        };
+        self.ranges.splice(from..to, [merged]);
+    }
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let from = self.ranges.partition_point(|r| r.end <= range.start);
+        let to = self.ranges.partition_point(|r| r.start < range.end);
+        let kept = self.ranges[from..to]
+            .iter()
+            .flat_map(|r| [r.start..range.start, range.end..r.end])
+            .filter(|r| !r.is_empty())
+            .collect::<Vec<_>>();
+        self.ranges.splice(from..to, kept);
+    }
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut union = self.clone();
+        other.iter().for_each(|r| union.insert(r.clone()));
+        union
+    }
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        other
+            .iter()
+            .flat_map(|r| self.clamp(r.clone()).ranges)
+            .collect()
+    }
+    /// The values in `self` that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut difference = self.clone();
+        other.iter().for_each(|r| difference.remove(r.clone()));
+        difference
+    }
+    /// The part of the set that lies within `bounds`.
+    pub fn clamp(&self, bounds: Range<T>) -> IntervalSet<T> {
+        self.iter()
+            .map(|r| r.start.max(bounds.start)..r.end.min(bounds.end))
+            .collect()
+    }
+    /// The uncovered ranges between the first and last covered value.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].end..w[1].start)
+    }
+    pub fn is_subset(&self, other: &IntervalSet<T>) -> bool {
+        self.difference(other).is_empty()
+    }
+    pub fn is_disjoint(&self, other: &IntervalSet<T>) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> IntervalSet<T> {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|r| set.insert(r));
+        set
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(range: Range<T>) -> IntervalSet<T> {
+        [range].into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<Range<i32>> {
+        set.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(5..8);
+        set.insert(0..2);
+        set.insert(10..12);
+        assert_eq!(ranges(&set), vec![0..2, 5..8, 10..12]);
+        set.insert(2..3);
+        set.insert(7..10);
+        assert_eq!(ranges(&set), vec![0..3, 5..12]);
+        set.insert(4..4);
+        assert_eq!(set.len(), 10);
+        assert!(set.contains(11) && !set.contains(12) && !set.contains(3));
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![3..5]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<i32> = [0..5, 10..15].into_iter().collect();
+        let b = IntervalSet::from(3..12);
+        assert_eq!(ranges(&a.union(&b)), vec![0..15]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![3..5, 10..12]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..3, 12..15]);
+        assert_eq!(ranges(&b.difference(&a)), vec![5..10]);
+        assert_eq!(ranges(&a.clamp(2..11)), vec![2..5, 10..11]);
+        assert!(IntervalSet::from(11..13).is_subset(&a));
+        assert!(!b.is_subset(&a));
+        assert!(IntervalSet::from(5..10).is_disjoint(&a));
+    }
+}
@@ -1,2 +1,8 @@
 pub mod coordinate;
+pub mod graph;
 pub mod grid;
+pub mod interval;
+pub mod ocr;
+pub mod pathfinding;
+pub mod region;
+pub mod render;
@@ -0,0 +1,121 @@
+use super::{
+    coordinate::{Bounds, Coordinate},
+    grid::Grid,
+    render,
+};
+
+const WIDTH: i32 = 4;
+const HEIGHT: i32 = 6;
+/// Glyph width plus the blank column separating letters.
+const SPACING: i32 = 5;
+
+/// The letters of the 4x6 font puzzles draw their answers in.
+const FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Reads the letters drawn on a screen 6 pixels high, with a glyph every 5
+/// columns and blank columns between them. Fails on the first glyph that is
+/// not in the font, or lit pixel between glyphs, saying which column it is.
+pub fn read(screen: &Grid<bool>) -> Result<String, String> {
+    if screen.height() as i32 != HEIGHT {
+        return Err(format!(
+            "screen is {} pixels high, expected {}",
+            screen.height(),
+            HEIGHT
+        ));
+    }
+    // The last glyph may or may not be followed by a blank column.
+    if !matches!(screen.width() as i32 % SPACING, 0 | WIDTH) {
+        return Err(format!(
+            "screen is {} pixels wide, which does not fit whole glyphs",
+            screen.width()
+        ));
+    }
+    (0..(screen.width() as i32 + 1) / SPACING)
+        .map(|i| {
+            let x = i * SPACING;
+            let glyph = render::draw_grid(
+                &screen.subgrid(&Bounds {
+                    min: Coordinate::new(x, 0),
+                    max: Coordinate::new(x + WIDTH - 1, HEIGHT - 1),
+                }),
+                false,
+                |lit| match lit {
+                    true => '#',
+                    false => '.',
+                },
+            );
+            let letter = FONT
+                .iter()
+                .find(|(_, shape)| *shape == glyph)
+                .map(|(letter, _)| *letter)
+                .ok_or_else(|| format!("unknown glyph at column {}", x + 1))?;
+            let separator = x + WIDTH;
+            if screen.contains(&Coordinate::new(separator, 0))
+                && screen.column(separator as usize).any(|lit| *lit)
+            {
+                return Err(format!(
+                    "lit pixel between glyphs at column {}",
+                    separator + 1
+                ));
+            }
+            Ok(letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(text: &str) -> Grid<bool> {
+        Grid::parse_with(text, |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_read() {
+        let text = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+        assert_eq!(read(&screen(text)), Ok("HELLO".to_owned()));
+        let padded = text.lines().map(|l| format!("{}.", l)).collect::<Vec<_>>();
+        assert_eq!(read(&screen(&padded.join("\n"))), Ok("HELLO".to_owned()));
+        let mut lit_separator = screen(text);
+        lit_separator[Coordinate::new(9, 2)] = true;
+        assert_eq!(
+            read(&lit_separator),
+            Err("lit pixel between glyphs at column 10".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let error = read(&Grid::new(9, 6, false)).unwrap_err();
+        assert_eq!(error, "unknown glyph at column 1");
+        assert!(read(&screen("####")).is_err());
+        assert!(read(&Grid::new(11, 6, false))
+            .unwrap_err()
+            .contains("11 pixels wide"));
+    }
+}
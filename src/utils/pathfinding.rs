@@ -0,0 +1,219 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// A path found by one of the searches, from a start node to a goal node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    /// Number of steps for `bfs`, sum of the step costs otherwise.
+    pub cost: usize,
+    /// Every node on the path, start and goal included.
+    pub nodes: Vec<N>,
+}
+
+/// Nodes seen so far, each with the index of the node it was reached from and
+/// the cost of reaching it.
+struct Visited<N> {
+    nodes: Vec<(N, Option<usize>, usize)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N> Visited<N>
+where
+    N: Eq + Hash + Clone,
+{
+    fn new() -> Visited<N> {
+        Visited {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+    /// Records `node` unless it was already reached at a cost of `cost` or
+    /// less, returning its index if it was recorded.
+    fn reach(&mut self, node: N, parent: Option<usize>, cost: usize) -> Option<usize> {
+        match self.index.entry(node) {
+            Entry::Vacant(entry) => {
+                let i = self.nodes.len();
+                self.nodes.push((entry.key().clone(), parent, cost));
+                entry.insert(i);
+                Some(i)
+            }
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                if cost >= self.nodes[i].2 {
+                    return None;
+                }
+                self.nodes[i].1 = parent;
+                self.nodes[i].2 = cost;
+                Some(i)
+            }
+        }
+    }
+    fn path(&self, mut i: usize) -> Path<N> {
+        let cost = self.nodes[i].2;
+        let mut nodes = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            nodes.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+}
+
+/// Breadth-first search from any of `starts` to the nearest node matching
+/// `goal`, counting every step as 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = starts
+        .into_iter()
+        .filter_map(|start| visited.reach(start, None, 0))
+        .collect::<VecDeque<_>>();
+    while let Some(i) = queue.pop_front() {
+        let (node, _, steps) = visited.nodes[i].clone();
+        if goal(&node) {
+            return Some(visited.path(i));
+        }
+        for next in neighbours(&node) {
+            if !visited.index.contains_key(&next) {
+                queue.extend(visited.reach(next, Some(i), steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from any of `starts` to a node matching `goal`, where
+/// `cost(from, to)` is the cost of stepping between neighbours.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    cost: impl FnMut(&N, &N) -> usize,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    astar(starts, neighbours, cost, |_| 0, goal)
+}
+
+/// Like `dijkstra`, guided by a `heuristic` that must never overestimate the
+/// remaining cost to a goal.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut cost: impl FnMut(&N, &N) -> usize,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(i) = visited.reach(start, None, 0) {
+            queue.push(Reverse((estimate, 0, i)));
+        }
+    }
+    while let Some(Reverse((_, spent, i))) = queue.pop() {
+        if spent > visited.nodes[i].2 {
+            continue;
+        }
+        let node = visited.nodes[i].0.clone();
+        if goal(&node) {
+            return Some(visited.path(i));
+        }
+        for next in neighbours(&node) {
+            let spent = spent + cost(&node, &next);
+            let estimate = spent + heuristic(&next);
+            if let Some(j) = visited.reach(next, Some(i), spent) {
+                queue.push(Reverse((estimate, spent, j)));
+            }
+        }
+    }
+    None
+}
+
+/// Every node reachable from any of `starts`, starts included.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut stack = starts.into_iter().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        if seen.contains(&node) {
+            continue;
+        }
+        stack.extend(neighbours(&node));
+        seen.insert(node);
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 in unit steps, plus a direct 0 -> 3 edge costing 5.
+    fn edges(n: &u32) -> Vec<(u32, usize)> {
+        match n {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn neighbours(n: &u32) -> Vec<u32> {
+        edges(n).into_iter().map(|(to, _)| to).collect()
+    }
+
+    fn cost(from: &u32, to: &u32) -> usize {
+        edges(from).into_iter().find(|(n, _)| n == to).unwrap().1
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs([0], neighbours, |n| *n == 3).unwrap();
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes, vec![0, 3]);
+        assert_eq!(
+            bfs([2, 0], neighbours, |n| *n == 3).unwrap().nodes,
+            vec![2, 3]
+        );
+        assert!(bfs([3], neighbours, |n| *n == 0).is_none());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra([0], neighbours, cost, |n| *n == 3).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        let path = astar([0], neighbours, cost, |n| 3 - *n as usize, |n| *n == 3).unwrap();
+        assert_eq!(path.cost, 3);
+    }
+
+    #[test]
+    fn test_reachable() {
+        assert_eq!(reachable([1], neighbours), HashSet::from([1, 2, 3]));
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    coordinate::{Bounds, Coordinate, Neighbourhood},
+    grid::Grid,
+    pathfinding,
+};
+
+/// A connected set of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub cells: HashSet<Coordinate>,
+    pub bounds: Bounds,
+}
+
+impl Region {
+    fn new(cells: HashSet<Coordinate>) -> Option<Region> {
+        let bounds = Bounds::of(&cells)?;
+        Some(Region { cells, bounds })
+    }
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+    /// The cells with at least one neighbour outside the region, in
+    /// ascending order.
+    pub fn boundary(&self, neighbourhood: Neighbourhood) -> Vec<Coordinate> {
+        let mut boundary = self
+            .cells
+            .iter()
+            .filter(|c| {
+                c.neighbours(neighbourhood)
+                    .any(|n| !self.cells.contains(&n))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        boundary.sort();
+        boundary
+    }
+    /// Number of cell sides that face a cell outside the region.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|c| c.neighbours(Neighbourhood::VonNeumann))
+            .filter(|n| !self.cells.contains(n))
+            .count()
+    }
+}
+
+/// The region of cells reachable from `seed` through cells whose values
+/// match `include`, or `None` if the seed itself does not match or lies
+/// outside the grid.
+pub fn flood_fill<S>(
+    grid: &Grid<S>,
+    seed: &Coordinate,
+    neighbourhood: Neighbourhood,
+    include: impl Fn(&S) -> bool,
+) -> Option<Region> {
+    if !include(grid.value_at(seed)?) {
+        return None;
+    }
+    Region::new(pathfinding::reachable([*seed], |c| {
+        grid.neighbours(c, neighbourhood)
+            .into_iter()
+            .filter(|(_, value)| include(value))
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>()
+    }))
+}
+
+/// Splits the grid into regions of connected, equal cells. Returns a grid
+/// holding each cell's index into the returned regions.
+pub fn label<S>(grid: &Grid<S>, neighbourhood: Neighbourhood) -> (Grid<usize>, Vec<Region>)
+where
+    S: PartialEq,
+{
+    let mut labels: HashMap<Coordinate, usize> = HashMap::new();
+    let mut regions = Vec::new();
+    for (c, value) in grid.iter() {
+        if labels.contains_key(&c) {
+            continue;
+        }
+        let region = flood_fill(grid, &c, neighbourhood, |v| v == value).unwrap();
+        labels.extend(region.cells.iter().map(|cell| (*cell, regions.len())));
+        regions.push(region);
+    }
+    (grid.map_with_coordinate(|c, _| labels[&c]), regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(
+            "aab
+abb
+bba",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = grid();
+        let seed = Coordinate::new(0, 0);
+        let region = flood_fill(&grid, &seed, Neighbourhood::VonNeumann, |v| *v == 'a').unwrap();
+        assert_eq!(region.size(), 3);
+        assert_eq!(region.bounds.max, Coordinate::new(1, 1));
+        assert_eq!(region.perimeter(), 8);
+        let region = flood_fill(&grid, &seed, Neighbourhood::Moore, |v| *v == 'a').unwrap();
+        assert_eq!(region.size(), 3);
+        assert!(flood_fill(&grid, &seed, Neighbourhood::Moore, |v| *v == 'b').is_none());
+    }
+
+    #[test]
+    fn test_label() {
+        let (labels, regions) = label(&grid(), Neighbourhood::VonNeumann);
+        assert_eq!(labels.to_string(), "001\n011\n112");
+        assert_eq!(
+            regions.iter().map(Region::size).collect::<Vec<_>>(),
+            vec![3, 5, 1]
+        );
+        let (_, regions) = label(&grid(), Neighbourhood::Moore);
+        assert_eq!(regions.len(), 3);
+    }
+
+    #[test]
+    fn test_boundary() {
+        let square = Grid::parse("###\n###\n###").unwrap();
+        let region = flood_fill(
+            &square,
+            &Coordinate::new(1, 1),
+            Neighbourhood::VonNeumann,
+            |v: &char| *v == '#',
+        )
+        .unwrap();
+        assert_eq!(region.boundary(Neighbourhood::VonNeumann).len(), 8);
+        assert!(!region
+            .boundary(Neighbourhood::Moore)
+            .contains(&Coordinate::new(1, 1)));
+        assert_eq!(region.perimeter(), 12);
+    }
+}
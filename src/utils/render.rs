@@ -0,0 +1,145 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
+use super::{
+    coordinate::{Bounds, Coordinate},
+    grid::Grid,
+};
+
+/// Coordinates row by row in drawing order. With `flip_y` the largest `y`
+/// comes first, so that up is up.
+fn rows(bounds: &Bounds, flip_y: bool) -> Vec<Vec<Coordinate>> {
+    let ys: Box<dyn Iterator<Item = i32>> = match flip_y {
+        true => Box::new((bounds.min.y..=bounds.max.y).rev()),
+        false => Box::new(bounds.min.y..=bounds.max.y),
+    };
+    ys.map(|y| {
+        (bounds.min.x..=bounds.max.x)
+            .map(|x| Coordinate::new(x, y))
+            .collect()
+    })
+    .collect()
+}
+
+/// Draws every cell within `bounds` as the char `cell` maps it to, one line
+/// per row.
+pub fn draw(bounds: &Bounds, flip_y: bool, cell: impl Fn(&Coordinate) -> char) -> String {
+    rows(bounds, flip_y)
+        .iter()
+        .map(|row| row.iter().map(&cell).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Draws a grid with `cell` mapping each value to a char.
+pub fn draw_grid<S>(grid: &Grid<S>, flip_y: bool, cell: impl Fn(&S) -> char) -> String {
+    draw(&grid.bounds(), flip_y, |c| cell(grid.value_at(c).unwrap()))
+}
+
+/// Draws `points` as `on` and the rest of their bounds as `off`.
+pub fn draw_points<'a>(
+    points: impl IntoIterator<Item = &'a Coordinate>,
+    on: char,
+    off: char,
+    flip_y: bool,
+) -> String {
+    let points = points.into_iter().collect::<HashSet<_>>();
+    match Bounds::of(points.iter().copied()) {
+        Some(bounds) => draw(&bounds, flip_y, |c| match points.contains(c) {
+            true => on,
+            false => off,
+        }),
+        None => String::new(),
+    }
+}
+
+impl<S> Display for Grid<S>
+where
+    S: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the cells within `bounds` as a binary greyscale PGM image, with
+/// `shade` giving each cell's brightness.
+pub fn write_pgm(
+    out: &mut impl Write,
+    bounds: &Bounds,
+    flip_y: bool,
+    shade: impl Fn(&Coordinate) -> u8,
+) -> io::Result<()> {
+    write!(out, "P5\n{} {}\n255\n", bounds.width(), bounds.height())?;
+    for row in rows(bounds, flip_y) {
+        out.write_all(&row.iter().map(&shade).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+/// Writes the cells within `bounds` as a binary colour PPM image, with
+/// `colour` giving each cell's red, green and blue.
+pub fn write_ppm(
+    out: &mut impl Write,
+    bounds: &Bounds,
+    flip_y: bool,
+    colour: impl Fn(&Coordinate) -> [u8; 3],
+) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", bounds.width(), bounds.height())?;
+    for row in rows(bounds, flip_y) {
+        out.write_all(&row.iter().flat_map(&colour).collect::<Vec<_>>())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_points() {
+        let points = [
+            Coordinate::new(1, 1),
+            Coordinate::new(2, 2),
+            Coordinate::new(3, 1),
+        ];
+        assert_eq!(draw_points(&points, '#', '.', false), "#.#\n.#.");
+        assert_eq!(draw_points(&points, '#', '.', true), ".#.\n#.#");
+        assert_eq!(draw_points(&[], '#', '.', false), "");
+    }
+
+    #[test]
+    fn test_draw_grid() {
+        let grid = Grid::<u8>::parse("12\n34").unwrap();
+        assert_eq!(grid.to_string(), "12\n34");
+        assert_eq!(
+            draw_grid(&grid, true, |v| match v % 2 {
+                0 => 'e',
+                _ => 'o',
+            }),
+            "oe\noe"
+        );
+    }
+
+    #[test]
+    fn test_write_images() {
+        let bounds = Bounds::of(&[Coordinate::new(0, 0), Coordinate::new(1, 0)]).unwrap();
+        let mut pgm = Vec::new();
+        write_pgm(&mut pgm, &bounds, false, |c| c.x as u8 * 255).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\x00\xff");
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &bounds, false, |c| [c.x as u8, 0, 1]).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\x01\x01\x00\x01");
+    }
+}